@@ -14,6 +14,68 @@ animation = Animation
 create-layout = Create layout
 apply-layout = Apply layout
 delete-layout = Delete layout
+hide-layout = Hide layout
+show-layout = Show
+hidden-layouts = Hidden layouts
+duplicate-layout = Duplicate and edit
+layout-copy = { $name } (copy)
+edit-layout = Edit layout
+new-layout = New layout
+import-layout = Import layout
+export-layout = Export layout
+install-layout = Install layout
+no-layouts-found = No layouts found
+downloads = {$downloads} downloads
+layout-catalog = Layout catalog
+layout-catalog-description = The address of an online layout catalog to browse in the Available tab.
+layout-catalog-placeholder = https://example.org/layouts
+no-layout-catalog = Set the address of a layout catalog in the settings to browse layouts.
+panels = Panels
+add-panel = Add panel
+add = Add
+editing = Editing
+opacity = Opacity
+applets-start = Start
+applets-center = Center
+applets-end = End
+drop-applet = Drop applets here
+output = Output
+panel-name = Panel name
+panel-name-empty = Panel name cannot be empty.
+panel-name-invalid = Panel names cannot start with a dot or contain slashes.
+panel-name-taken = A panel with this name already exists.
+delete-panel = Delete { $name }?
+delete = Delete
+delete-panel-description = The panel is removed from the desktop and its settings are deleted.
+placement = Placement
+anchor-gap = Gap from the edge
+anchor-gap-description = Leave space between the panel and the edge of the screen.
+exclusive-zone = Reserve space
+exclusive-zone-description = Keep windows from overlapping the panel.
+layer = Layer
+layer-description = Which windows the panel is drawn above.
+layer-background = Background
+layer-bottom = Bottom
+layer-top = Top
+layer-overlay = Overlay
+keyboard-interactivity = Keyboard interactivity
+keyboard-none = None
+keyboard-exclusive = Exclusive
+keyboard-on-demand = On demand
+background = Background
+same-as-panel = Same as panel
+size-start = Start size
+size-center = Center size
+size-end = End size
+autohide = Automatically hide
+autohide-description = Hide the panel until the pointer reaches its edge.
+applets = Applets
+add-applets-to = Add applets to
+installed-applets = Installed applets
+all-outputs = All outputs
+active-output = Active output
+disconnected-output = { $name } (disconnected)
+other-outputs = Other outputs
 layout-name = Layout name
 layout-name-empty = Layout name cannot be empty.
 dock-icons = Dock icons
@@ -21,6 +83,7 @@ show-window = Show window
 preview = Preview
 layout-exists-error = A layout with this name already exists.
 layout-name-required = Name is required.
+incompatible-layouts = Incompatible layouts
 panel-properties = Panel properties
 panel-position = Panel position
 panel-size = Panel size
@@ -61,11 +124,18 @@ most-downloaded = Most downloaded
 last-modified = Last modified
 author = Author
 search = Search
+search-settings = Search settings
+search-settings-description = Find a setting, layout, snapshot or shortcut scheme.
+no-results = Nothing matches your search.
 
 ## Panel
 show-panel = Show panel
 show-dock = Show dock
-force-icon-buttons-in-panel = Force icon buttons in panel
+button-presentation = Button presentation
+button-presentation-description = Whether applet buttons on this panel show an icon or text.
+presentation-default = Default
+presentation-icon = Icon
+presentation-text = Text
 size = Size
 size-description = The height of the panel and applets
 padding = Padding
@@ -94,6 +164,14 @@ navigation = Navigation
 
 save-current-layout = Save current layout
 save-current-layout-description = Save the current desktop layout for future use.
+presets = Presets
+layout = Layout
+panel-buttons = Panel buttons
+preset-name = Preset name
+save-current-preset = Save current desktop
+save-current-preset-description = Save the current panels, color scheme and panel buttons as a preset.
+apply-preset = Apply preset
+delete-preset = Delete preset
 save-current-color-scheme = Save current color scheme
 color-scheme-name = Color scheme name
 create-snapshot = Create snapshot
@@ -112,6 +190,10 @@ user = User
 ## Snapshots
 application-opened = Application opened
 restore-info = Each time you open Tweaks, we save the current state of your desktop, if you ever break it, simply restore it
+incompatible-snapshot = Incompatible snapshot
+delete-incompatible-snapshot = Delete incompatible snapshot?
+delete-incompatible-snapshot-description = { $name } was written by another version of Tweaks and can't be restored. Deleting it removes the file for good.
+version = Version {$version}
 
 ## About
 about = About
@@ -135,5 +217,113 @@ view = View
 
 ## Shortcuts
 
-warning = Warning: this will override some of your existing custom shortcuts
+shortcuts-description = Schemes add their bindings to your custom shortcuts. Removing a scheme puts back the shortcuts it replaced.
+shortcut-schemes = Shortcut schemes
+applied = Applied
+apply-scheme = Apply scheme
+remove-scheme = Remove scheme
+replaces-custom = Replaces your shortcut: { $action }
+replaces-system = Replaces the system shortcut: { $action }
+resolve-shortcuts = Apply { $scheme } shortcuts
+resolve-shortcuts-description = Some bindings of this scheme are already in use. Choose what happens to each of them.
+conflicts = Conflicts
+keep = Keep current
+replace = Replace
+rebind = Use another binding
+binding-placeholder = Super+Shift+K
+binding-invalid = "{ $binding }" is not a valid binding
+binding-taken = { $binding } is already in use
+changes = Changes
+shortcut-change = { $binding }: { $from } → { $to }
+shortcut-add = { $binding }: { $action }
+no-shortcut-changes = No shortcuts will change.
+custom-shortcuts = Custom shortcuts
+add-shortcut = Add shortcut
+edit-shortcut = Edit shortcut
+binding = Binding
+set-binding = Set binding
+press-keys = Press a key combination…
+binding-missing = Set a binding first
+action = Action
+action-invalid = Choose an action, commands can't be empty
+argument = Argument
+command = Command
+save-as-group = Save as group
+save-as-group-description = Saves your custom shortcuts as a group you can apply again or share.
+shortcut-group-name = Group name
+import-group = Import group
+incompatible-shortcut-groups = Incompatible shortcut groups
+shortcut-cheat-sheet = Cheat sheet
+export-cheat-sheet = Export the shortcuts in effect
+category-windows = Windows
+category-tiling = Tiling
+category-workspaces = Workspaces
+category-outputs = Displays
+category-applications = Applications
+category-system = System
 windows-desc = Super+Arrows to move windows. Ctrl+Alt+Arrows to navigate workspaces.
+macos-desc = Super stands in for Command. Super+Space searches, Ctrl+Arrows switch workspaces.
+gnome-desc = Super+Page Up/Down switch workspaces, Super+A opens the app library.
+kde-desc = Ctrl+F1–F4 switch workspaces, Alt+Space opens the launcher.
+tiling-desc = Super+H/J/K/L to focus windows, Super+Shift+H/J/K/L to move them.
+
+## Profiles
+profiles = Profiles
+save-profile = Save as profile
+save-profile-description = Captures the theme, panels, dock, layout and shortcuts so you can switch back to them later.
+profile-name = Profile name
+profile-active = Active
+profile-drifted = Active, changed since it was applied
+profile-modified = { $name } (modified)
+switch = Switch
+update-profile = Update with the current desktop
+next-profile = Next profile
+switch-profile = Switch to { $name }?
+switch-profile-description = The theme, panels, dock, layout and shortcuts will be replaced by the ones saved in this profile.
+delete-profile = Delete { $name }?
+delete-profile-description = The profile is removed for good. The desktop itself is left as it is.
+
+## Errors
+error-log = Error log
+no-errors = Nothing went wrong this session.
+retry = Retry
+details = Details
+clear = Clear
+failed-apply-color-scheme = Failed to apply the color scheme
+failed-import-color-scheme = Failed to import the color scheme
+failed-install-color-scheme = Failed to install the color scheme
+failed-save-color-scheme = Failed to save the current color scheme
+failed-read-current-theme = Failed to read the current theme
+failed-save-current-color-scheme = Failed to remember the applied color scheme
+failed-fetch-color-schemes = Failed to fetch color schemes
+failed-open-link = Failed to open the link
+failed-apply-layout = Failed to apply the layout
+failed-load-layouts = Failed to load the layouts
+failed-save-layout = Failed to save the layout
+failed-read-layout = Failed to read the current layout
+failed-delete-layout = Failed to delete the layout
+failed-import-layout = Failed to import the layout
+failed-install-layout = Failed to install the layout
+failed-export-layout = Failed to export the layout
+failed-fetch-layouts = Failed to fetch layouts
+failed-apply-preset = Failed to apply the preset
+failed-save-preset = Failed to save the preset
+failed-delete-preset = Failed to delete the preset
+failed-save-panel = Failed to save the { $name } settings
+failed-create-panel = Failed to create { $name }
+failed-delete-panel = Failed to delete { $name }
+failed-save-applets = Failed to save the applets
+failed-create-snapshot = Failed to create the snapshot
+failed-restore-snapshot = Failed to restore the snapshot
+failed-delete-snapshot = Failed to delete the snapshot
+failed-load-snapshots = Failed to load the snapshots
+failed-save-shortcuts = Failed to save the shortcuts
+failed-import-shortcuts = Failed to import the shortcut group
+failed-export-shortcuts = Failed to export the shortcut group
+failed-save-shortcut-group = Failed to save the shortcut group
+failed-delete-shortcut-group = Failed to delete the shortcut group
+failed-export-cheat-sheet = Failed to export the cheat sheet
+failed-switch-profile = Failed to switch profiles
+failed-save-profile = Failed to save the profile
+failed-delete-profile = Failed to delete the profile
+failed-save-settings = Failed to save the settings
@@ -6,17 +6,23 @@ use cosmic::{
     iced, widget,
 };
 use std::collections::{HashMap, VecDeque};
+use std::sync::{
+    Arc,
+    atomic::{AtomicU64, Ordering},
+};
+use std::time::Duration;
 
 use dialog::DialogPage;
 use flags::Flags;
 use message::Message;
 
-use crate::app::message::SettingsMessage;
+use crate::app::message::{ProfileMessage, SettingsMessage};
 
 pub mod action;
 pub mod context;
 pub mod context_drawer;
 pub mod core;
+pub mod dbus;
 pub mod dialog;
 pub mod flags;
 pub mod footer;
@@ -26,10 +32,16 @@ pub mod message;
 pub mod nav;
 pub mod page;
 pub mod pages;
+pub mod report;
+pub mod search;
 pub mod subscription;
 pub mod update;
 pub mod view;
 
+/// How long config changes settle before the desktop is compared with the
+/// active profile.
+const PROFILE_DRIFT_DELAY: Duration = Duration::from_millis(500);
+
 pub struct App {
     cosmic: Cosmic,
     handler: cosmic::cosmic_config::Config,
@@ -37,6 +49,8 @@ pub struct App {
     color_schemes: pages::ColorSchemes,
     dock: pages::Dock,
     panel: pages::Panel,
+    panels: pages::Panels,
+    applets: pages::Applets,
     layouts: pages::Layouts,
     snapshots: pages::Snapshots,
     shortcuts: pages::ShortcutsPage,
@@ -52,6 +66,16 @@ pub struct Cosmic {
     modifiers: iced::keyboard::Modifiers,
     context_page: ContextPage,
     app_themes: Vec<String>,
+    search: search::Search,
+    /// Whether the desktop changed since the active profile was applied.
+    profile_drift: bool,
+    /// Counts drift checks so only the latest one compares the desktop.
+    profile_drift_check: Arc<AtomicU64>,
+    /// The session bus connection holding Tweaks' name, `None` without a bus.
+    dbus: Option<zbus::Connection>,
+    toasts: widget::toaster::Toasts<Message>,
+    /// The failures of this session, shown in the error log.
+    errors: Vec<report::Report>,
 }
 
 impl Application for App {
@@ -112,7 +136,7 @@ impl Application for App {
     }
 
     fn subscription(&self) -> cosmic::iced::Subscription<Self::Message> {
-        Cosmic::subscription()
+        Cosmic::subscription(self)
     }
 }
 
@@ -121,6 +145,98 @@ impl App {
         cosmic::command::set_theme(self.config.app_theme.theme())
     }
 
+    /// Compares the desktop with the active profile once config changes
+    /// settle, as capturing the desktop reads every config it manages.
+    fn check_profile_drift(&mut self) -> Task<Message> {
+        let latest = self.cosmic.profile_drift_check.clone();
+        let check = latest.fetch_add(1, Ordering::Relaxed) + 1;
+        let Some(profile) = self.config.active_profile().cloned() else {
+            self.cosmic.profile_drift = false;
+            return Task::none();
+        };
+        Task::future(async move {
+            tokio::time::sleep(PROFILE_DRIFT_DELAY).await;
+            // A later change started its own check.
+            if latest.load(Ordering::Relaxed) != check {
+                return None;
+            }
+            let name = profile.name.clone();
+            let drifted = match tokio::task::spawn_blocking(move || profile.drifted()).await {
+                Ok(Ok(drifted)) => drifted,
+                Ok(Err(e)) => {
+                    log::warn!("Failed to compare the desktop with profile {name}: {e}");
+                    false
+                }
+                Err(e) => {
+                    log::warn!("Failed to compare the desktop with profile {name}: {e}");
+                    false
+                }
+            };
+            Some(drifted)
+        })
+        .and_then(move |drifted| {
+            Task::done(cosmic::action::app(Message::ProfileDrift(check, drifted)))
+        })
+    }
+
+    fn profiles<'a>(&'a self) -> Element<'a, Message> {
+        let spacing = cosmic::theme::spacing();
+        let section = widget::settings::section()
+            .title(crate::fl!("profiles"))
+            .add(
+                widget::settings::item::builder(crate::fl!("save-profile"))
+                    .description(crate::fl!("save-profile-description"))
+                    .control(widget::button::standard(crate::fl!("save")).on_press(
+                        Message::ToggleDialogPage(DialogPage::CreateProfile(String::new())),
+                    )),
+            );
+
+        self.config
+            .profiles
+            .iter()
+            .enumerate()
+            .fold(section, |section, (index, profile)| {
+                let active = self.config.active_profile == Some(profile.id);
+                let status = match (active, self.cosmic.profile_drift) {
+                    (true, true) => crate::fl!("profile-drifted"),
+                    (true, false) => crate::fl!("profile-active"),
+                    (false, _) => String::new(),
+                };
+                section.add(
+                    widget::settings::item::builder(profile.name.clone())
+                        .description(status)
+                        .control(
+                            widget::row()
+                                .push(
+                                    widget::button::standard(crate::fl!("switch")).on_press_maybe(
+                                        (!active || self.cosmic.profile_drift).then_some(
+                                            Message::Profile(ProfileMessage::Switch(index)),
+                                        ),
+                                    ),
+                                )
+                                .push(widget::tooltip(
+                                    widget::button::icon(widget::icon::from_name(
+                                        "document-save-symbolic",
+                                    ))
+                                    .on_press(Message::Profile(ProfileMessage::Update(index))),
+                                    widget::text(crate::fl!("update-profile")),
+                                    widget::tooltip::Position::Bottom,
+                                ))
+                                .push(
+                                    widget::button::icon(widget::icon::from_name(
+                                        "user-trash-symbolic",
+                                    ))
+                                    .class(cosmic::style::Button::Destructive)
+                                    .on_press(Message::Profile(ProfileMessage::Delete(index))),
+                                )
+                                .spacing(spacing.space_xxs)
+                                .align_y(iced::Alignment::Center),
+                        ),
+                )
+            })
+            .into()
+    }
+
     fn settings<'a>(&'a self) -> Element<'a, Message> {
         let app_theme_selected = match self.config.app_theme {
             core::config::AppTheme::Dark => 1,
@@ -138,6 +254,23 @@ impl App {
                     )),
                 )
                 .into(),
+            widget::settings::section()
+                .title(crate::fl!("layouts"))
+                .add(
+                    widget::settings::item::builder(crate::fl!("layout-catalog"))
+                        .description(crate::fl!("layout-catalog-description"))
+                        .control(
+                            widget::text_input(
+                                crate::fl!("layout-catalog-placeholder"),
+                                &self.config.layout_catalog_url,
+                            )
+                            .on_input(|url| {
+                                Message::Settings(SettingsMessage::LayoutCatalogUrl(url))
+                            }),
+                        ),
+                )
+                .into(),
+            self.profiles(),
         ])
         .into()
     }
@@ -1,9 +1,14 @@
-use super::{Message, context::ContextPage};
+use super::{Message, context::ContextPage, dialog::DialogPage, message::ProfileMessage};
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum TweaksAction {
     About,
     Settings,
+    Search,
+    ErrorLog,
+    SwitchProfile(usize),
+    NextProfile,
+    SaveProfile,
 }
 
 impl cosmic::widget::menu::Action for TweaksAction {
@@ -12,6 +17,13 @@ impl cosmic::widget::menu::Action for TweaksAction {
         match self {
             TweaksAction::About => Message::ToggleContextPage(ContextPage::About),
             TweaksAction::Settings => Message::ToggleContextPage(ContextPage::Settings),
+            TweaksAction::Search => Message::ToggleContextPage(ContextPage::Search),
+            TweaksAction::ErrorLog => Message::ToggleContextPage(ContextPage::ErrorLog),
+            TweaksAction::SwitchProfile(index) => Message::Profile(ProfileMessage::Switch(index)),
+            TweaksAction::NextProfile => Message::Profile(ProfileMessage::Next),
+            TweaksAction::SaveProfile => {
+                Message::ToggleDialogPage(DialogPage::CreateProfile(String::new()))
+            }
         }
     }
 }
@@ -4,6 +4,8 @@ use crate::fl;
 pub enum ContextPage {
     Settings,
     About,
+    Search,
+    ErrorLog,
 }
 
 impl ContextPage {
@@ -11,6 +13,8 @@ impl ContextPage {
         match self {
             Self::About => fl!("about"),
             Self::Settings => fl!("settings"),
+            Self::Search => fl!("search-settings"),
+            Self::ErrorLog => fl!("error-log"),
         }
     }
 }
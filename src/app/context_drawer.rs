@@ -25,6 +25,15 @@ impl Cosmic {
                 app::context_drawer::context_drawer(app.settings(), Message::ToggleContextDrawer)
                     .title(app.cosmic.context_page.title())
             }
+            ContextPage::Search => app::context_drawer::context_drawer(
+                app.cosmic.search.view(),
+                Message::ToggleContextDrawer,
+            )
+            .title(app.cosmic.context_page.title()),
+            ContextPage::ErrorLog => {
+                app::context_drawer::context_drawer(app.error_log(), Message::ToggleContextDrawer)
+                    .title(app.cosmic.context_page.title())
+            }
         })
     }
 }
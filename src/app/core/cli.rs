@@ -0,0 +1,224 @@
+use std::path::PathBuf;
+
+use crate::{
+    Error,
+    app::{
+        core::{config::TweaksConfig, desired::Desired, profile},
+        page::Page,
+    },
+};
+
+const USAGE: &str = "Usage: cosmic-ext-tweaks [--page <page> | --dialog <dialog> | \
+                     --apply-scheme <name> | cosmic-tweaks://<page or dialog> | \
+                     --profile <name> | check [<file>] | apply [<file>]]";
+
+/// Links such as `cosmic-tweaks://dock` open Tweaks on a page or dialog.
+const URI_SCHEME: &str = "cosmic-tweaks://";
+
+/// What a launch asks the window to do, handed to the running instance if
+/// there is one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Request {
+    Page(Page),
+    Dialog(Dialog),
+    ApplyScheme(String),
+}
+
+impl Request {
+    /// Reads a link by the name of the page or dialog it points to.
+    pub fn from_uri(uri: &str) -> Result<Self, Error> {
+        let target = uri
+            .strip_prefix(URI_SCHEME)
+            .unwrap_or_default()
+            .trim_matches('/');
+        Page::from_name(target)
+            .map(Request::Page)
+            .or_else(|| Dialog::from_name(target).map(Request::Dialog))
+            .ok_or_else(|| {
+                Error::Arguments(format!(
+                    "unknown link {uri}, expected {URI_SCHEME} followed by one of {}, {}",
+                    page_names(),
+                    dialog_names()
+                ))
+            })
+    }
+}
+
+/// A dialog a launch can open, on the page it belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dialog {
+    SaveScheme,
+    ImportScheme,
+    SaveLayout,
+    ImportLayout,
+    ImportShortcuts,
+    CreateSnapshot,
+}
+
+impl Dialog {
+    pub const ALL: [Dialog; 6] = [
+        Dialog::SaveScheme,
+        Dialog::ImportScheme,
+        Dialog::SaveLayout,
+        Dialog::ImportLayout,
+        Dialog::ImportShortcuts,
+        Dialog::CreateSnapshot,
+    ];
+
+    /// The name of the dialog on the command line, such as `create-snapshot`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Dialog::SaveScheme => "save-scheme",
+            Dialog::ImportScheme => "import-scheme",
+            Dialog::SaveLayout => "save-layout",
+            Dialog::ImportLayout => "import-layout",
+            Dialog::ImportShortcuts => "import-shortcuts",
+            Dialog::CreateSnapshot => "create-snapshot",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|dialog| dialog.name().eq_ignore_ascii_case(name))
+    }
+
+    pub fn page(self) -> Page {
+        match self {
+            Dialog::SaveScheme | Dialog::ImportScheme => Page::ColorSchemes,
+            Dialog::SaveLayout | Dialog::ImportLayout => Page::Layouts,
+            Dialog::ImportShortcuts => Page::Shortcuts,
+            Dialog::CreateSnapshot => Page::Snapshots,
+        }
+    }
+}
+
+fn page_names() -> String {
+    let pages: Vec<&str> = Page::all().iter().map(Page::name).collect();
+    pages.join(", ")
+}
+
+fn dialog_names() -> String {
+    let dialogs: Vec<&str> = Dialog::ALL.into_iter().map(Dialog::name).collect();
+    dialogs.join(", ")
+}
+
+/// What Tweaks was asked to do on the command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// Opens the window, or raises the one already open.
+    Gui(Option<Request>),
+    /// Switches to a profile without opening the window, so it can be bound
+    /// to a system shortcut.
+    Profile(String),
+    /// Reports how the desktop differs from a desired configuration.
+    Check(Option<PathBuf>),
+    /// Converges the desktop to a desired configuration.
+    Apply(Option<PathBuf>),
+    Help,
+}
+
+impl Command {
+    pub fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, Error> {
+        let Some(arg) = args.next() else {
+            return Ok(Command::Gui(None));
+        };
+
+        let command = match arg.as_str() {
+            "--page" => {
+                let name = args
+                    .next()
+                    .ok_or_else(|| Error::Arguments(format!("--page takes a page. {USAGE}")))?;
+                let page = Page::from_name(&name).ok_or_else(|| {
+                    Error::Arguments(format!(
+                        "unknown page {name}, expected one of {}",
+                        page_names()
+                    ))
+                })?;
+                Command::Gui(Some(Request::Page(page)))
+            }
+            "--dialog" => {
+                let name = args
+                    .next()
+                    .ok_or_else(|| Error::Arguments(format!("--dialog takes a dialog. {USAGE}")))?;
+                let dialog = Dialog::from_name(&name).ok_or_else(|| {
+                    Error::Arguments(format!(
+                        "unknown dialog {name}, expected one of {}",
+                        dialog_names()
+                    ))
+                })?;
+                Command::Gui(Some(Request::Dialog(dialog)))
+            }
+            uri if uri.starts_with(URI_SCHEME) => Command::Gui(Some(Request::from_uri(uri)?)),
+            "--apply-scheme" => Command::Gui(Some(Request::ApplyScheme(args.next().ok_or_else(
+                || Error::Arguments(format!("--apply-scheme takes a name. {USAGE}")),
+            )?))),
+            "--profile" | "-p" => Command::Profile(
+                args.next()
+                    .ok_or_else(|| Error::Arguments(format!("--profile takes a name. {USAGE}")))?,
+            ),
+            "check" => Command::Check(args.next().map(PathBuf::from)),
+            "apply" => Command::Apply(args.next().map(PathBuf::from)),
+            "--help" | "-h" => Command::Help,
+            other => {
+                return Err(Error::Arguments(format!(
+                    "unknown argument {other}. {USAGE}"
+                )));
+            }
+        };
+
+        match args.next() {
+            Some(extra) => Err(Error::Arguments(format!(
+                "unexpected argument {extra}. {USAGE}"
+            ))),
+            None => Ok(command),
+        }
+    }
+
+    /// Runs a command that doesn't open the window.
+    pub fn run(self) -> Result<(), Error> {
+        match self {
+            Command::Gui(_) => Ok(()),
+            Command::Help => {
+                println!("{USAGE}");
+                Ok(())
+            }
+            Command::Profile(name) => {
+                let mut config = TweaksConfig::new();
+                let profile = profile::find(&config.profiles, &name)
+                    .cloned()
+                    .ok_or(Error::ProfileNotFound(name))?;
+                profile.apply()?;
+                config.set_active_profile(&TweaksConfig::config(), Some(profile.id))?;
+                println!("Switched to profile {}", profile.name);
+                Ok(())
+            }
+            Command::Check(path) => {
+                let drift = read_desired(path)?.check()?;
+                for setting in &drift {
+                    println!("{setting}");
+                }
+                if drift.is_empty() {
+                    println!("The desktop matches the desired configuration");
+                    Ok(())
+                } else {
+                    Err(Error::Drifted(drift.len()))
+                }
+            }
+            Command::Apply(path) => {
+                let desired = read_desired(path)?;
+                desired.apply()?;
+                println!("Applied the desired configuration");
+                Ok(())
+            }
+        }
+    }
+}
+
+fn read_desired(path: Option<PathBuf>) -> Result<Desired, Error> {
+    let path = match path {
+        Some(path) => path,
+        None => Desired::default_path()?,
+    };
+    Desired::read(&path)
+}
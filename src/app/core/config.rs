@@ -4,14 +4,27 @@ use cosmic::{
     theme,
 };
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
-use crate::app::App;
+use crate::app::{App, core::profile::Profile, pages::shortcuts::scheme::AppliedScheme};
 
 pub const CONFIG_VERSION: u64 = 1;
 
-#[derive(Clone, Default, Debug, Eq, PartialEq, Deserialize, Serialize, CosmicConfigEntry)]
+#[derive(Clone, Default, Debug, PartialEq, Deserialize, Serialize, CosmicConfigEntry)]
 pub struct TweaksConfig {
     pub app_theme: AppTheme,
+    /// The endpoint of the online layout catalog, none is used when empty.
+    pub layout_catalog_url: String,
+    /// Built-in layouts the user chose not to see.
+    pub hidden_layouts: Vec<Uuid>,
+    /// Shortcut schemes added to the custom shortcuts, in the order they were applied.
+    pub shortcut_schemes: Vec<AppliedScheme>,
+    /// Named captures of the desktop the user can switch between.
+    pub profiles: Vec<Profile>,
+    /// The profile last switched to.
+    pub active_profile: Option<Uuid>,
+    /// The layout last applied, as the panels don't record where they came from.
+    pub applied_layout: Option<Uuid>,
 }
 
 impl TweaksConfig {
@@ -28,6 +41,11 @@ impl TweaksConfig {
             config
         })
     }
+
+    pub fn active_profile(&self) -> Option<&Profile> {
+        let id = self.active_profile?;
+        self.profiles.iter().find(|profile| profile.id == id)
+    }
 }
 
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
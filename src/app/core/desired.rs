@@ -0,0 +1,446 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use cosmic::cosmic_config::{ConfigSet, CosmicConfigEntry};
+use cosmic_ext_config_templates::{Schema, load_template};
+use cosmic_panel_config::{CosmicPanelConfig, PanelAnchor, PanelSize};
+use cosmic_settings_config::{Shortcuts, shortcuts};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Error,
+    app::{
+        core::{
+            config::TweaksConfig,
+            versioned::{self, Versioned},
+        },
+        pages::{
+            color_schemes::{self, ColorScheme},
+            layouts::{
+                config::Layout,
+                outputs,
+                preset::{current_schema, same_schema},
+            },
+            shortcuts::{
+                ShortcutsGroup, custom_shortcuts,
+                scheme::{self, AppliedScheme, Scheme},
+            },
+        },
+    },
+};
+
+/// The desktop a machine should converge to, written once and shared across
+/// a fleet.
+///
+/// Everything is optional, parts left out are neither checked nor applied.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Desired {
+    #[serde(default)]
+    pub version: u32,
+    /// The name of an installed color scheme.
+    #[serde(default)]
+    pub color_scheme: Option<String>,
+    /// The name of a built-in or custom layout.
+    #[serde(default)]
+    pub layout: Option<String>,
+    /// Values of the panels, by entry name such as "Panel" or "Dock".
+    #[serde(default)]
+    pub panels: BTreeMap<String, PanelValues>,
+    /// The shortcut groups applied, any other applied group is removed.
+    #[serde(default)]
+    pub shortcut_groups: Option<Vec<ShortcutsGroup>>,
+}
+
+/// The panel settings a desired configuration can pin.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PanelValues {
+    pub anchor: Option<PanelAnchor>,
+    pub size: Option<PanelSize>,
+    pub padding: Option<u32>,
+    pub margin: Option<u16>,
+    pub spacing: Option<u32>,
+    pub border_radius: Option<u32>,
+    pub opacity: Option<f32>,
+    pub expand_to_edges: Option<bool>,
+    pub anchor_gap: Option<bool>,
+    pub exclusive_zone: Option<bool>,
+}
+
+/// A setting whose live value differs from the desired one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Drift {
+    pub setting: String,
+    pub desired: String,
+    pub live: String,
+}
+
+impl Display for Drift {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {} (desired {})",
+            self.setting, self.live, self.desired
+        )
+    }
+}
+
+/// Compares a pinned panel value with the live one.
+macro_rules! check_value {
+    ($drift:expr, $panel:expr, $values:expr, $config:expr, $($field:ident),*) => {
+        $(
+            if let Some(desired) = &$values.$field
+                && *desired != $config.$field
+            {
+                $drift.push(Drift {
+                    setting: format!("{}.{}", $panel, stringify!($field)),
+                    desired: format!("{desired:?}"),
+                    live: format!("{:?}", $config.$field),
+                });
+            }
+        )*
+    };
+}
+
+/// Writes the pinned panel values that differ from the live ones.
+macro_rules! apply_value {
+    ($helper:expr, $values:expr, $config:expr, $($field:ident => $setter:ident),*) => {
+        $(
+            if let Some(desired) = &$values.$field
+                && *desired != $config.$field
+            {
+                $config.$setter($helper, desired.clone())?;
+            }
+        )*
+    };
+}
+
+impl Desired {
+    /// `~/.config/cosmic-tweaks/desired.ron`, read when no file is given.
+    pub fn default_path() -> Result<PathBuf, Error> {
+        dirs::config_dir()
+            .map(|path| path.join("cosmic-tweaks").join("desired.ron"))
+            .ok_or(Error::ConfigDirNotFound)
+    }
+
+    pub fn read(path: &Path) -> Result<Self, Error> {
+        let contents = std::fs::read_to_string(path)?;
+        versioned::from_str(&contents)
+    }
+
+    /// Lists every setting of the live desktop that differs from this file.
+    pub fn check(&self) -> Result<Vec<Drift>, Error> {
+        Ok(self.drift(&Live::read(self)?))
+    }
+
+    fn drift(&self, live: &Live) -> Vec<Drift> {
+        let mut drift = Vec::new();
+
+        if let Some(name) = &self.color_scheme
+            && !live.color_scheme
+        {
+            drift.push(Drift {
+                setting: "color_scheme".into(),
+                desired: name.clone(),
+                live: "another color scheme".into(),
+            });
+        }
+
+        if let (Some(name), Some((desired, schema))) = (&self.layout, &live.layout)
+            && !same_schema(desired, schema)
+        {
+            drift.push(Drift {
+                setting: "layout".into(),
+                desired: name.clone(),
+                live: "another layout".into(),
+            });
+        }
+
+        for (panel, values) in &self.panels {
+            let Some(config) = live.panels.get(panel) else {
+                continue;
+            };
+            check_value!(
+                drift,
+                panel,
+                values,
+                config,
+                anchor,
+                size,
+                padding,
+                margin,
+                spacing,
+                border_radius,
+                opacity,
+                expand_to_edges,
+                anchor_gap,
+                exclusive_zone
+            );
+        }
+
+        if let Some(groups) = &self.shortcut_groups {
+            for group in groups {
+                let applied = live
+                    .shortcut_schemes
+                    .iter()
+                    .find(|applied| applied.group == *group);
+                let intact = applied.is_some_and(|applied| {
+                    applied
+                        .bindings
+                        .iter()
+                        .all(|(binding, action)| live.shortcuts.0.get(binding) == Some(action))
+                });
+                if !intact {
+                    drift.push(Drift {
+                        setting: "shortcut_groups".into(),
+                        desired: format!("{group:?}"),
+                        live: if applied.is_some() {
+                            "changed since it was applied".into()
+                        } else {
+                            "not applied".into()
+                        },
+                    });
+                }
+            }
+            for applied in &live.shortcut_schemes {
+                if !groups.contains(&applied.group) {
+                    drift.push(Drift {
+                        setting: "shortcut_groups".into(),
+                        desired: "not applied".into(),
+                        live: format!("{:?}", applied.group),
+                    });
+                }
+            }
+        }
+
+        drift
+    }
+
+    /// Converges the live desktop to this file, writing only what drifted.
+    ///
+    /// The layout goes first, so the panel values of the file are written on
+    /// top of it.
+    pub fn apply(&self) -> Result<(), Error> {
+        let tweaks_config = TweaksConfig::config();
+        let mut tweaks = TweaksConfig::new();
+
+        if let Some(name) = &self.color_scheme {
+            let desired = find_color_scheme(name)?;
+            let live = color_schemes::get_current_theme().ok();
+            if live.as_ref() != Some(&desired.theme_builder) {
+                color_schemes::apply_theme(&desired.theme)
+                    .map_err(|e| Error::Apply(format!("color scheme: {e}")))?;
+            }
+        }
+
+        if let Some(name) = &self.layout {
+            let desired = find_layout(name)?;
+            let schema = outputs::resolve_schema(desired.schema);
+            if !same_schema(&schema, &current_schema()?) {
+                load_template(schema).map_err(|e| Error::Apply(format!("layout: {e}")))?;
+            }
+            if tweaks.applied_layout != Some(desired.id) {
+                tweaks.set_applied_layout(&tweaks_config, Some(desired.id))?;
+            }
+        }
+
+        for (panel, values) in &self.panels {
+            let helper = CosmicPanelConfig::cosmic_config(panel)?;
+            let mut config =
+                CosmicPanelConfig::get_entry(&helper).unwrap_or_else(|(_, config)| config);
+            apply_value!(
+                &helper,
+                values,
+                config,
+                anchor => set_anchor,
+                size => set_size,
+                padding => set_padding,
+                margin => set_margin,
+                spacing => set_spacing,
+                border_radius => set_border_radius,
+                opacity => set_opacity,
+                expand_to_edges => set_expand_to_edges,
+                anchor_gap => set_anchor_gap,
+                exclusive_zone => set_exclusive_zone
+            );
+        }
+
+        if let Some(groups) = &self.shortcut_groups {
+            let config = shortcuts::context()?;
+            let mut custom = custom_shortcuts(&config);
+            let mut applied = tweaks.shortcut_schemes.clone();
+            let mut changed = false;
+
+            for group in tweaks.shortcut_schemes.iter().map(|applied| &applied.group) {
+                if !groups.contains(group) {
+                    scheme::remove(group, &mut custom, &mut applied);
+                    changed = true;
+                }
+            }
+            for group in groups {
+                let scheme = Scheme::load(group).ok_or_else(|| {
+                    Error::Apply(format!("shortcuts: the group {group:?} doesn't exist"))
+                })?;
+                let intact = applied.iter().any(|applied| {
+                    applied.group == *group
+                        && applied
+                            .bindings
+                            .iter()
+                            .all(|(binding, action)| custom.0.get(binding) == Some(action))
+                });
+                if !intact {
+                    scheme::apply(&scheme, &mut custom, &mut applied, &[]);
+                    changed = true;
+                }
+            }
+
+            if changed {
+                config.set("custom", custom)?;
+                tweaks.set_shortcut_schemes(&tweaks_config, applied)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The parts of the live desktop a desired configuration pins, read once so
+/// they can be compared.
+#[derive(Default)]
+struct Live {
+    /// Whether the desired color scheme is the one in use.
+    color_scheme: bool,
+    /// The schema of the desired layout on the connected outputs, and the
+    /// schema of the panels in use.
+    layout: Option<(Schema, Schema)>,
+    panels: BTreeMap<String, CosmicPanelConfig>,
+    shortcuts: Shortcuts,
+    shortcut_schemes: Vec<AppliedScheme>,
+}
+
+impl Live {
+    fn read(desired: &Desired) -> Result<Self, Error> {
+        let mut live = Live::default();
+
+        if let Some(name) = &desired.color_scheme {
+            let scheme = find_color_scheme(name)?;
+            let theme = color_schemes::get_current_theme()
+                .map_err(|e| Error::Apply(format!("color scheme: {e}")))?;
+            live.color_scheme = theme == scheme.theme_builder;
+        }
+
+        if let Some(name) = &desired.layout {
+            let layout = find_layout(name)?;
+            live.layout = Some((outputs::resolve_schema(layout.schema), current_schema()?));
+        }
+
+        for panel in desired.panels.keys() {
+            let helper = CosmicPanelConfig::cosmic_config(panel)?;
+            let config = CosmicPanelConfig::get_entry(&helper).unwrap_or_else(|(_, config)| config);
+            live.panels.insert(panel.clone(), config);
+        }
+
+        if desired.shortcut_groups.is_some() {
+            live.shortcuts = custom_shortcuts(&shortcuts::context()?);
+            live.shortcut_schemes = TweaksConfig::new().shortcut_schemes;
+        }
+
+        Ok(live)
+    }
+}
+
+impl Versioned for Desired {
+    const VERSION: u32 = 1;
+
+    fn version(&self) -> u32 {
+        self.version
+    }
+
+    fn migrate(_contents: &str, version: u32) -> Result<Self, Error> {
+        Err(Error::UnsupportedVersion(version))
+    }
+}
+
+fn find_color_scheme(name: &str) -> Result<ColorScheme, Error> {
+    color_schemes::installed_system_themes()
+        .map_err(|e| Error::Apply(format!("color scheme: {e}")))?
+        .into_iter()
+        .find(|scheme| scheme.name == name)
+        .ok_or_else(|| Error::Apply(format!("color scheme: {name} is not installed")))
+}
+
+fn find_layout(name: &str) -> Result<Layout, Error> {
+    Layout::list()?
+        .0
+        .into_iter()
+        .find(|layout| layout.name.eq_ignore_ascii_case(name))
+        .ok_or_else(|| Error::Apply(format!("layout: {name} doesn't exist")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = r#"(
+        version: 1,
+        layout: Some("Mac"),
+        panels: {
+            "Dock": (size: Some(L), opacity: Some(0.5)),
+        },
+        shortcut_groups: Some([Gnome]),
+    )"#;
+
+    fn bundled_schema(slug: &str) -> Schema {
+        Layout::bundled_by_slug()
+            .into_iter()
+            .find(|(bundled, _)| *bundled == slug)
+            .map(|(_, layout)| layout.schema)
+            .unwrap()
+    }
+
+    fn live(layout: &str) -> Live {
+        let dock = CosmicPanelConfig {
+            size: PanelSize::L,
+            opacity: 0.5,
+            ..Default::default()
+        };
+        Live {
+            layout: Some((bundled_schema("mac"), bundled_schema(layout))),
+            panels: BTreeMap::from([("Dock".to_string(), dock)]),
+            shortcut_schemes: vec![AppliedScheme {
+                group: ShortcutsGroup::Gnome,
+                bindings: Vec::new(),
+                replaced: Vec::new(),
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn settings(drift: &[Drift]) -> Vec<&str> {
+        drift.iter().map(|drift| drift.setting.as_str()).collect()
+    }
+
+    #[test]
+    fn matching_desktop_has_no_drift() {
+        let desired: Desired = versioned::from_str(FIXTURE).unwrap();
+        assert!(desired.drift(&live("mac")).is_empty());
+    }
+
+    #[test]
+    fn reports_each_drifted_setting() {
+        let desired: Desired = versioned::from_str(FIXTURE).unwrap();
+        let mut live = live("windows");
+        live.panels.get_mut("Dock").unwrap().size = PanelSize::S;
+        live.shortcut_schemes[0].group = ShortcutsGroup::Kde;
+
+        let drift = desired.drift(&live);
+        assert_eq!(
+            settings(&drift),
+            ["layout", "Dock.size", "shortcut_groups", "shortcut_groups"]
+        );
+        assert_eq!(drift[1].desired, "L");
+        assert_eq!(drift[1].live, "S");
+    }
+}
@@ -6,10 +6,44 @@ pub enum Error {
     Io(#[from] std::io::Error),
     #[error("RON error: {0}")]
     Ron(#[from] ron::error::SpannedError),
+    #[error("RON error: {0}")]
+    RonSerialize(#[from] ron::Error),
     #[error("Iced error: {0}")]
     Iced(#[from] cosmic::iced::Error),
     #[error("Theme path not found")]
     ThemePathNotFound,
     #[error("Layout path not found")]
     LayoutPathNotFound,
+    #[error("Data directory not found")]
+    DataDirNotFound,
+    #[error("Config directory not found")]
+    ConfigDirNotFound,
+    #[error("Unsupported format version {0}")]
+    UnsupportedVersion(u32),
+    #[error("Invalid layout: {0}")]
+    InvalidLayout(String),
+    #[error("Invalid shortcut group: {0}")]
+    InvalidShortcuts(String),
+    #[error("No local file was selected")]
+    NoFileSelected,
+    #[error("Config error: {0}")]
+    Config(#[from] cosmic::cosmic_config::Error),
+    #[error("Failed to apply {0}")]
+    Apply(String),
+    #[error("No profile named {0}")]
+    ProfileNotFound(String),
+    #[error("Invalid arguments: {0}")]
+    Arguments(String),
+    #[error("{0} settings differ from the desired configuration")]
+    Drifted(usize),
+    #[error("D-Bus error: {0}")]
+    DBus(#[from] zbus::Error),
+    #[error("Color scheme error: {0}")]
+    ColorScheme(String),
+    #[error("Template error: {0}")]
+    Template(String),
+    #[error("Catalog error: {0}")]
+    Catalog(String),
+    #[error("File picker error: {0}")]
+    FilePicker(#[from] ashpd::Error),
 }
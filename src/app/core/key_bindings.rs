@@ -26,6 +26,8 @@ impl KeyBindings {
 
         bind!([Ctrl], Key::Character(",".into()), Settings);
         bind!([Ctrl], Key::Character("i".into()), About);
+        bind!([Ctrl], Key::Character("f".into()), Search);
+        bind!([Ctrl], Key::Character("p".into()), NextProfile);
 
         key_binds
     }
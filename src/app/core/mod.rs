@@ -1,7 +1,11 @@
+pub mod cli;
 pub mod config;
+pub mod desired;
 pub mod error;
 pub mod grid;
 pub mod icons;
 pub mod key_bindings;
+pub mod profile;
 pub mod settings;
 pub mod style;
+pub mod versioned;
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{Error, app::pages::layouts::preset::DesktopState};
+
+/// A named snapshot of everything Tweaks manages, such as "Work" or
+/// "Presentation", that can be switched to as a whole.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Profile {
+    pub id: Uuid,
+    pub name: String,
+    pub state: DesktopState,
+}
+
+impl Profile {
+    /// Captures the theme, panels, dock, layout and shortcuts in use.
+    pub fn current(name: String) -> Result<Self, Error> {
+        Ok(Self {
+            id: Uuid::new_v4(),
+            name,
+            state: DesktopState::capture()?,
+        })
+    }
+
+    /// Writes the whole profile, putting the desktop back if any part fails.
+    pub fn apply(&self) -> Result<(), Error> {
+        DesktopState::rollback_on_error(&format!("profile {}", self.name), || {
+            self.state.clone().restore()
+        })
+    }
+
+    /// Whether the desktop was changed since the profile was applied or saved.
+    pub fn drifted(&self) -> Result<bool, Error> {
+        Ok(DesktopState::capture()? != self.state)
+    }
+}
+
+/// Finds a profile by its name, ignoring case as names are typed on the command line.
+pub fn find<'a>(profiles: &'a [Profile], name: &str) -> Option<&'a Profile> {
+    profiles
+        .iter()
+        .find(|profile| profile.name.eq_ignore_ascii_case(name))
+}
@@ -1,4 +1,7 @@
-use crate::{app::flags::Flags, localize};
+use crate::{
+    app::{core::cli::Request, flags::Flags},
+    localize,
+};
 
 pub fn settings() -> cosmic::app::Settings {
     cosmic::app::Settings::default().size_limits(
@@ -8,10 +11,12 @@ pub fn settings() -> cosmic::app::Settings {
     )
 }
 
-pub fn flags() -> Flags {
+pub fn flags(request: Option<Request>, dbus: Option<zbus::Connection>) -> Flags {
     Flags {
         handler: crate::app::core::config::TweaksConfig::config(),
         config: crate::app::core::config::TweaksConfig::new(),
+        request,
+        dbus,
     }
 }
 
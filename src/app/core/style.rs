@@ -55,6 +55,22 @@ pub fn panel_style(theme: &cosmic::Theme) -> widget::container::Style {
     }
 }
 
+/// Outlines the control the global search jumped to.
+pub fn highlight(theme: &cosmic::Theme) -> widget::container::Style {
+    let theme = theme.cosmic();
+    cosmic::widget::container::Style {
+        icon_color: None,
+        text_color: None,
+        background: None,
+        border: Border {
+            color: theme.accent.base.into(),
+            width: 2.0,
+            radius: theme.corner_radii.radius_s.into(),
+        },
+        shadow: Shadow::default(),
+    }
+}
+
 pub fn standard_button(theme: Arc<Theme>) -> Button {
     let theme_active = theme.clone();
     let theme_disabled = theme.clone();
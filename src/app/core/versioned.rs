@@ -0,0 +1,255 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use crate::Error;
+
+/// A file format stored on disk that carries a version and can be upgraded.
+pub trait Versioned: Serialize + DeserializeOwned + Sized {
+    /// The version written by this build of Tweaks.
+    const VERSION: u32;
+
+    fn version(&self) -> u32;
+
+    /// Reads a file written with an older version of the format.
+    ///
+    /// The file is handed over as text so each version can be deserialized
+    /// with the shape it had, then converted, which lets fields be renamed,
+    /// removed or change type between versions.
+    fn migrate(contents: &str, version: u32) -> Result<Self, Error>;
+}
+
+/// A file that exists on disk but could not be read with the current format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Incompatible {
+    pub path: PathBuf,
+    pub name: String,
+    pub version: u32,
+    pub reason: String,
+}
+
+/// The fields shared by every versioned file, read before the full format.
+#[derive(Deserialize)]
+struct Header {
+    #[serde(default)]
+    version: u32,
+    #[serde(default)]
+    name: String,
+}
+
+/// Reads a versioned file, migrating it to the current version if needed.
+///
+/// Migrated files are written back so the upgrade only happens once.
+pub fn read<T: Versioned>(path: &Path) -> Result<T, Incompatible> {
+    let incompatible = |name: String, version: u32, reason: String| Incompatible {
+        path: path.to_path_buf(),
+        name: if name.is_empty() {
+            path.file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default()
+        } else {
+            name
+        },
+        version,
+        reason,
+    };
+
+    let contents =
+        std::fs::read_to_string(path).map_err(|e| incompatible(String::new(), 0, e.to_string()))?;
+
+    let Header { version, name } = ron::from_str::<Header>(&contents)
+        .map_err(|e| incompatible(String::new(), 0, e.to_string()))?;
+
+    let value = from_str::<T>(&contents).map_err(|e| incompatible(name, version, e.to_string()))?;
+    let migrated = version < T::VERSION;
+
+    if migrated {
+        match ron::ser::to_string_pretty(&value, ron::ser::PrettyConfig::default()) {
+            Ok(data) => {
+                if let Err(e) = std::fs::write(path, data) {
+                    log::warn!("Failed to write migrated file {}: {e}", path.display());
+                }
+            }
+            Err(e) => log::warn!("Failed to serialize migrated file {}: {e}", path.display()),
+        }
+    }
+
+    Ok(value)
+}
+
+/// Parses a versioned value from a string, migrating it in memory.
+pub fn from_str<T: Versioned>(contents: &str) -> Result<T, Error> {
+    let Header { version, .. } = ron::from_str::<Header>(contents)?;
+
+    if version > T::VERSION {
+        return Err(Error::UnsupportedVersion(version));
+    }
+
+    let value = if version == T::VERSION {
+        ron::from_str::<T>(contents)?
+    } else {
+        T::migrate(contents, version)?
+    };
+
+    if value.version() != T::VERSION {
+        return Err(Error::UnsupportedVersion(version));
+    }
+
+    Ok(value)
+}
+
+/// Reads every `.ron` file in a directory, separating incompatible files.
+pub fn read_dir<T: Versioned>(dir: &Path) -> (Vec<T>, Vec<Incompatible>) {
+    let mut values = Vec::new();
+    let mut incompatible = Vec::new();
+
+    if let Ok(entries) = std::fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|s| s.to_str()) != Some("ron") {
+                continue;
+            }
+            match read::<T>(&path) {
+                Ok(value) => values.push(value),
+                Err(error) => {
+                    log::warn!(
+                        "Incompatible file {}: {}",
+                        error.path.display(),
+                        error.reason
+                    );
+                    incompatible.push(error);
+                }
+            }
+        }
+    }
+
+    (values, incompatible)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A format whose second version renamed `text` to `body`.
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Note {
+        #[serde(default)]
+        version: u32,
+        name: String,
+        body: String,
+    }
+
+    #[derive(Deserialize)]
+    struct NoteV1 {
+        name: String,
+        text: String,
+    }
+
+    impl Versioned for Note {
+        const VERSION: u32 = 2;
+
+        fn version(&self) -> u32 {
+            self.version
+        }
+
+        fn migrate(contents: &str, version: u32) -> Result<Self, Error> {
+            match version {
+                0 | 1 => {
+                    let note: NoteV1 = ron::from_str(contents)?;
+                    Ok(Self {
+                        version: Self::VERSION,
+                        name: note.name,
+                        body: note.text,
+                    })
+                }
+                version => Err(Error::UnsupportedVersion(version)),
+            }
+        }
+    }
+
+    fn note(body: &str) -> Note {
+        Note {
+            version: Note::VERSION,
+            name: "note".into(),
+            body: body.into(),
+        }
+    }
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("cosmic-tweaks-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn reads_current_version() {
+        let value = from_str::<Note>(r#"(version: 2, name: "note", body: "hi")"#).unwrap();
+        assert_eq!(value, note("hi"));
+    }
+
+    #[test]
+    fn migrates_older_versions() {
+        let value = from_str::<Note>(r#"(version: 1, name: "note", text: "hi")"#).unwrap();
+        assert_eq!(value, note("hi"));
+
+        let value = from_str::<Note>(r#"(name: "note", text: "hi")"#).unwrap();
+        assert_eq!(value, note("hi"));
+    }
+
+    #[test]
+    fn rejects_newer_versions() {
+        let error = from_str::<Note>(r#"(version: 3, name: "note", body: "hi")"#).unwrap_err();
+        assert!(matches!(error, Error::UnsupportedVersion(3)));
+    }
+
+    #[test]
+    fn rejects_broken_files() {
+        assert!(from_str::<Note>(r#"(version: 2, name: "note""#).is_err());
+        assert!(from_str::<Note>(r#"(version: 2, name: "note")"#).is_err());
+    }
+
+    #[test]
+    fn read_dir_separates_incompatible_files() {
+        let dir = temp_dir();
+        let files = [
+            (
+                "current.ron",
+                r#"(version: 2, name: "current", body: "hi")"#,
+            ),
+            ("older.ron", r#"(version: 1, name: "older", text: "hi")"#),
+            ("newer.ron", r#"(version: 3, name: "newer", body: "hi")"#),
+            ("broken.ron", r#"(version: 2, name: "#),
+            ("ignored.txt", "not a versioned file"),
+        ];
+        for (name, contents) in files {
+            std::fs::write(dir.join(name), contents).unwrap();
+        }
+
+        let (mut values, mut incompatible) = read_dir::<Note>(&dir);
+        values.sort_by(|a, b| a.name.cmp(&b.name));
+        incompatible.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let names: Vec<&str> = values.iter().map(|note| note.name.as_str()).collect();
+        assert_eq!(names, ["current", "older"]);
+        assert!(values.iter().all(|note| note.version == Note::VERSION));
+
+        let names: Vec<(&str, u32)> = incompatible
+            .iter()
+            .map(|file| (file.name.as_str(), file.version))
+            .collect();
+        assert_eq!(names, [("broken", 0), ("newer", 3)]);
+
+        // The migrated file was written back in the current format.
+        let older = std::fs::read_to_string(dir.join("older.ron")).unwrap();
+        assert_eq!(
+            from_str::<Note>(&older).unwrap(),
+            Note {
+                name: "older".into(),
+                ..note("hi")
+            }
+        );
+        assert!(older.contains("body"));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,589 @@
+use std::any::TypeId;
+use std::time::Duration;
+
+use cosmic::{
+    Application,
+    app::Task,
+    iced::{
+        Subscription,
+        futures::{SinkExt, channel::mpsc},
+        stream,
+    },
+};
+use uuid::Uuid;
+use zbus::{
+    fdo::{self, RequestNameFlags},
+    interface,
+    object_server::SignalEmitter,
+};
+
+use crate::Error;
+use crate::app::{
+    App,
+    core::cli::{Dialog, Request},
+    message::{Message, SettingsMessage},
+    page::Page,
+    pages::{
+        self, color_schemes,
+        layouts::config::Layout,
+        snapshots::{Snapshots, config::Snapshot, config::SnapshotKind},
+    },
+};
+
+/// Where Tweaks is served, under its app id as the bus name.
+pub const PATH: &str = "/dev/edfloreshz/CosmicTweaks";
+
+/// A change announced to other apps, whether it came from the bus or the window.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Signal {
+    ColorSchemeChanged,
+    LayoutApplied(Uuid),
+    SnapshotsChanged,
+    SnapshotRestored(Uuid),
+}
+
+impl Signal {
+    async fn emit(&self, connection: &zbus::Connection) -> zbus::Result<()> {
+        let emitter = SignalEmitter::new(connection, PATH)?;
+        match self {
+            Signal::ColorSchemeChanged => Service::color_scheme_changed(&emitter).await,
+            Signal::LayoutApplied(id) => Service::layout_applied(&emitter, &id.to_string()).await,
+            Signal::SnapshotsChanged => Service::snapshots_changed(&emitter).await,
+            Signal::SnapshotRestored(id) => {
+                Service::snapshot_restored(&emitter, &id.to_string()).await
+            }
+        }
+    }
+}
+
+/// Lets panel applets, scripts and automation drive Tweaks.
+///
+/// Calls run the same operations as the pages and return their result, the
+/// window is then told so it shows the change.
+pub struct Service {
+    output: mpsc::Sender<Message>,
+}
+
+impl Service {
+    async fn forward(&self, message: Message) -> fdo::Result<()> {
+        self.output
+            .clone()
+            .send(message)
+            .await
+            .map_err(|e| fdo::Error::Failed(e.to_string()))
+    }
+
+    /// Tells the window about a change that already succeeded.
+    async fn notify(&self, message: Message) {
+        if let Err(e) = self.output.clone().send(message).await {
+            log::error!("Failed to update the window after a D-Bus call: {e}");
+        }
+    }
+}
+
+#[interface(name = "dev.edfloreshz.CosmicTweaks")]
+impl Service {
+    /// Raises the window.
+    ///
+    /// `token` is the xdg-activation token of the caller, empty without one.
+    async fn activate(&self, token: String) -> fdo::Result<()> {
+        self.forward(Message::Activate(None, activation_token(token)))
+            .await
+    }
+
+    /// Raises the window on a page, by its command line name.
+    async fn show_page(&self, page: String, token: String) -> fdo::Result<()> {
+        let page = Page::from_name(&page)
+            .ok_or_else(|| fdo::Error::InvalidArgs(format!("no page named {page}")))?;
+        self.forward(Message::Activate(
+            Some(Request::Page(page)),
+            activation_token(token),
+        ))
+        .await
+    }
+
+    /// Raises the window with a dialog open, by its command line name.
+    async fn open_dialog(&self, dialog: String, token: String) -> fdo::Result<()> {
+        let dialog = Dialog::from_name(&dialog)
+            .ok_or_else(|| fdo::Error::InvalidArgs(format!("no dialog named {dialog}")))?;
+        self.forward(Message::Activate(
+            Some(Request::Dialog(dialog)),
+            activation_token(token),
+        ))
+        .await
+    }
+
+    /// The names of the installed color schemes.
+    async fn list_color_schemes(&self) -> fdo::Result<Vec<String>> {
+        color_scheme_names()
+    }
+
+    async fn apply_color_scheme(&self, name: String) -> fdo::Result<()> {
+        let color_scheme = color_schemes::installed_system_themes()
+            .map_err(|e| fdo::Error::Failed(e.to_string()))?
+            .into_iter()
+            .find(|color_scheme| color_scheme.name == name)
+            .ok_or_else(|| fdo::Error::InvalidArgs(format!("no color scheme named {name}")))?;
+        color_schemes::apply_theme(&color_scheme.theme)
+            .map_err(|e| fdo::Error::Failed(e.to_string()))?;
+        self.notify(Message::ColorSchemes(Box::new(
+            pages::color_schemes::Message::Applied(color_scheme),
+        )))
+        .await;
+        Ok(())
+    }
+
+    /// The ids and names of the built-in and custom layouts.
+    async fn list_layouts(&self) -> fdo::Result<Vec<(String, String)>> {
+        Ok(layouts()?
+            .into_iter()
+            .map(|layout| (layout.id.to_string(), layout.name))
+            .collect())
+    }
+
+    /// Applies a layout by its id or name.
+    async fn apply_layout(&self, layout: String) -> fdo::Result<()> {
+        let layout = layouts()?
+            .into_iter()
+            .find(|l| l.id.to_string() == layout || l.name.eq_ignore_ascii_case(&layout))
+            .ok_or_else(|| fdo::Error::InvalidArgs(format!("no layout named {layout}")))?;
+        layout.apply().map_err(failed)?;
+        self.notify(Message::Settings(SettingsMessage::AppliedLayout(Some(
+            layout.id,
+        ))))
+        .await;
+        Ok(())
+    }
+
+    /// The ids and names of the snapshots.
+    async fn list_snapshots(&self) -> fdo::Result<Vec<(String, String)>> {
+        Ok(Snapshots::list()
+            .map_err(failed)?
+            .0
+            .into_iter()
+            .map(|snapshot| (snapshot.id.to_string(), snapshot.name))
+            .collect())
+    }
+
+    async fn create_snapshot(&self, name: String) -> fdo::Result<()> {
+        Snapshot::new(name, SnapshotKind::User)
+            .save()
+            .map_err(failed)?;
+        self.notify(Message::Snapshots(pages::snapshots::Message::Changed))
+            .await;
+        Ok(())
+    }
+
+    /// Restores a snapshot by its id or name.
+    async fn restore_snapshot(&self, snapshot: String) -> fdo::Result<()> {
+        let snapshot = find_snapshot(&snapshot)?
+            .ok_or_else(|| fdo::Error::InvalidArgs(format!("no snapshot named {snapshot}")))?;
+        snapshot.restore().map_err(failed)?;
+        self.notify(Message::Snapshots(pages::snapshots::Message::Restored(
+            snapshot.id,
+        )))
+        .await;
+        Ok(())
+    }
+
+    #[zbus(signal)]
+    async fn color_scheme_changed(emitter: &SignalEmitter<'_>) -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    async fn layout_applied(emitter: &SignalEmitter<'_>, id: &str) -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    async fn snapshots_changed(emitter: &SignalEmitter<'_>) -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    async fn snapshot_restored(emitter: &SignalEmitter<'_>, id: &str) -> zbus::Result<()>;
+}
+
+fn color_scheme_names() -> fdo::Result<Vec<String>> {
+    let mut names: Vec<String> = color_schemes::installed_system_themes()
+        .map_err(|e| fdo::Error::Failed(e.to_string()))?
+        .into_iter()
+        .map(|color_scheme| color_scheme.name)
+        .collect();
+    names.sort();
+    names.dedup();
+    Ok(names)
+}
+
+fn layouts() -> fdo::Result<Vec<Layout>> {
+    Layout::list().map(|(layouts, _)| layouts).map_err(failed)
+}
+
+fn activation_token(token: String) -> Option<String> {
+    (!token.is_empty()).then_some(token)
+}
+
+fn failed(error: Error) -> fdo::Error {
+    fdo::Error::Failed(error.to_string())
+}
+
+fn find_snapshot(snapshot: &str) -> fdo::Result<Option<Snapshot>> {
+    Ok(Snapshots::list()
+        .map_err(failed)?
+        .0
+        .into_iter()
+        .find(|s| s.id.to_string() == snapshot || s.name == snapshot))
+}
+
+/// How a launch relates to the instance holding the bus name.
+pub enum Instance {
+    /// This launch holds the name and opens the window, served on the
+    /// connection when there is a session bus.
+    Primary(Option<zbus::Connection>),
+    /// The launch was handed over to the instance already running.
+    Forwarded,
+}
+
+/// Claims the bus name before the window opens, so a single window edits the
+/// configs and launches racing each other can't both end up with one.
+///
+/// The bus comes from `DBUS_SESSION_BUS_ADDRESS`, so running Tweaks under
+/// `dbus-run-session` serves it on a private bus. Without a session bus there
+/// is no way to tell, and a window is opened.
+pub fn claim(request: Option<&Request>) -> zbus::Result<Instance> {
+    let Ok(connection) = zbus::blocking::Connection::session() else {
+        return Ok(Instance::Primary(None));
+    };
+    match connection.request_name_with_flags(App::APP_ID, RequestNameFlags::DoNotQueue.into()) {
+        Ok(_) => Ok(Instance::Primary(Some(connection.into()))),
+        Err(zbus::Error::NameTaken) => {
+            forward(&connection, request)?;
+            Ok(Instance::Forwarded)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Hands a launch over to the instance holding the name, passing on the
+/// activation token the launcher gave so the window may take focus.
+fn forward(connection: &zbus::blocking::Connection, request: Option<&Request>) -> zbus::Result<()> {
+    let token = std::env::var("XDG_ACTIVATION_TOKEN").unwrap_or_default();
+    let with_token = |method: &str, argument: Option<&str>| match argument {
+        Some(argument) => call(connection, method, &(argument, token.as_str())),
+        None => call(connection, method, &(token.as_str(),)),
+    };
+    match request {
+        Some(Request::Page(page)) => with_token("ShowPage", Some(page.name()))?,
+        Some(Request::Dialog(dialog)) => with_token("OpenDialog", Some(dialog.name()))?,
+        Some(Request::ApplyScheme(name)) => {
+            call(connection, "ApplyColorScheme", &(name.as_str(),))?;
+            with_token("Activate", None)?
+        }
+        None => with_token("Activate", None)?,
+    };
+    Ok(())
+}
+
+/// How often a forwarded call is retried while the instance holding the name
+/// hasn't served its object yet, and how long to wait between attempts.
+const SERVE_RETRIES: u32 = 20;
+const SERVE_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+/// Calls the instance holding the name.
+///
+/// The name is claimed before the window opens but the object is only served
+/// once it runs, so a launch racing a starting instance waits for it.
+fn call<B>(
+    connection: &zbus::blocking::Connection,
+    method: &str,
+    body: &B,
+) -> zbus::Result<zbus::Message>
+where
+    B: serde::Serialize + zbus::zvariant::DynamicType,
+{
+    let mut attempts = 0;
+    loop {
+        match connection.call_method(Some(App::APP_ID), PATH, Some(App::APP_ID), method, body) {
+            Err(zbus::Error::MethodError(name, _, _))
+                if attempts < SERVE_RETRIES
+                    && matches!(
+                        name.as_str(),
+                        "org.freedesktop.DBus.Error.UnknownObject"
+                            | "org.freedesktop.DBus.Error.UnknownInterface"
+                            | "org.freedesktop.DBus.Error.UnknownMethod"
+                    ) =>
+            {
+                attempts += 1;
+                std::thread::sleep(SERVE_RETRY_DELAY);
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Serves Tweaks for as long as the window is open, on the connection that
+/// claimed its name.
+pub fn subscription(connection: zbus::Connection) -> Subscription<Message> {
+    struct ServiceSubscription;
+
+    Subscription::run_with_id(
+        TypeId::of::<ServiceSubscription>(),
+        stream::channel(4, move |output: mpsc::Sender<Message>| async move {
+            if let Err(e) = connection
+                .object_server()
+                .at(PATH, Service { output })
+                .await
+            {
+                log::error!("Failed to start the D-Bus service: {e}");
+            }
+            std::future::pending::<()>().await;
+        }),
+    )
+}
+
+impl App {
+    /// Announces a change on the bus, once Tweaks is served there.
+    pub fn emit(&self, signal: Signal) -> Task<Message> {
+        let Some(connection) = self.cosmic.dbus.clone() else {
+            return Task::none();
+        };
+        Task::future(async move {
+            if let Err(e) = signal.emit(&connection).await {
+                log::error!("Failed to emit {signal:?}: {e}");
+            }
+        })
+        .discard()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader};
+    use std::process::{Child, Command, Stdio};
+    use std::sync::Once;
+
+    use cosmic::iced::futures::StreamExt;
+    use zbus::{MatchRule, MessageStream, message};
+
+    use super::*;
+
+    /// A session bus of its own, so tests neither see nor disturb the user's.
+    struct Bus {
+        daemon: Child,
+        address: String,
+    }
+
+    impl Bus {
+        fn start() -> Self {
+            isolate_data_dir();
+            let mut daemon = Command::new("dbus-daemon")
+                .args(["--session", "--nofork", "--print-address"])
+                .stdout(Stdio::piped())
+                .spawn()
+                .expect("dbus-daemon is needed to run the D-Bus tests");
+            let mut address = String::new();
+            BufReader::new(daemon.stdout.take().unwrap())
+                .read_line(&mut address)
+                .unwrap();
+            Self {
+                daemon,
+                address: address.trim().to_owned(),
+            }
+        }
+
+        async fn connect(&self) -> zbus::Connection {
+            zbus::connection::Builder::address(self.address.as_str())
+                .unwrap()
+                .build()
+                .await
+                .unwrap()
+        }
+
+        /// Serves a `Service` under the app id, as the window does.
+        async fn serve(&self) -> (zbus::Connection, mpsc::Receiver<Message>) {
+            let (output, messages) = mpsc::channel(4);
+            let connection = zbus::connection::Builder::address(self.address.as_str())
+                .unwrap()
+                .serve_at(PATH, Service { output })
+                .unwrap()
+                .name(App::APP_ID)
+                .unwrap()
+                .build()
+                .await
+                .unwrap();
+            (connection, messages)
+        }
+    }
+
+    impl Drop for Bus {
+        fn drop(&mut self) {
+            let _ = self.daemon.kill();
+            let _ = self.daemon.wait();
+        }
+    }
+
+    /// Points the data dir at an empty directory, so listing and creating
+    /// layouts and snapshots never touches the user's.
+    fn isolate_data_dir() {
+        static ISOLATE: Once = Once::new();
+        ISOLATE.call_once(|| {
+            let dir = std::env::temp_dir().join(format!("cosmic-tweaks-dbus-{}", Uuid::new_v4()));
+            std::fs::create_dir_all(&dir).unwrap();
+            // SAFETY: set once, before the first test of this module reads it.
+            unsafe { std::env::set_var("XDG_DATA_HOME", &dir) };
+        });
+    }
+
+    async fn call<B>(
+        connection: &zbus::Connection,
+        method: &str,
+        body: &B,
+    ) -> zbus::Result<zbus::Message>
+    where
+        B: serde::Serialize + zbus::zvariant::DynamicType,
+    {
+        connection
+            .call_method(Some(App::APP_ID), PATH, Some(App::APP_ID), method, body)
+            .await
+    }
+
+    fn error_name(result: zbus::Result<zbus::Message>) -> String {
+        match result {
+            Err(zbus::Error::MethodError(name, _, _)) => name.to_string(),
+            result => panic!("expected a D-Bus error, got {result:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn owns_the_app_id() {
+        let bus = Bus::start();
+        let (server, _messages) = bus.serve().await;
+        let client = bus.connect().await;
+
+        let owner = fdo::DBusProxy::new(&client)
+            .await
+            .unwrap()
+            .get_name_owner(App::APP_ID.try_into().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(server.unique_name(), Some(&owner));
+
+        // A second launch is told the name is taken and forwards instead.
+        assert!(matches!(
+            client
+                .request_name_with_flags(App::APP_ID, RequestNameFlags::DoNotQueue.into())
+                .await,
+            Err(zbus::Error::NameTaken)
+        ));
+    }
+
+    #[tokio::test]
+    async fn lists_layouts() {
+        let bus = Bus::start();
+        let (_server, _messages) = bus.serve().await;
+        let client = bus.connect().await;
+        let reply = call(&client, "ListLayouts", &()).await.unwrap();
+        let layouts: Vec<(String, String)> = reply.body().deserialize().unwrap();
+        for name in ["Cosmic", "Mac", "Windows", "Ubuntu"] {
+            assert!(layouts.iter().any(|(_, layout)| layout == name));
+        }
+    }
+
+    #[tokio::test]
+    async fn apply_returns_the_failure() {
+        let bus = Bus::start();
+        let (_server, mut messages) = bus.serve().await;
+        let client = bus.connect().await;
+        assert_eq!(
+            error_name(call(&client, "ApplyLayout", &("No such layout",)).await),
+            "org.freedesktop.DBus.Error.InvalidArgs"
+        );
+        assert_eq!(
+            error_name(call(&client, "RestoreSnapshot", &("No such snapshot",)).await),
+            "org.freedesktop.DBus.Error.InvalidArgs"
+        );
+        assert_eq!(
+            error_name(call(&client, "ShowPage", &("no-such-page", "")).await),
+            "org.freedesktop.DBus.Error.InvalidArgs"
+        );
+        // The window is only told about calls that succeeded.
+        assert!(messages.try_next().is_err());
+    }
+
+    #[tokio::test]
+    async fn forwards_window_requests() {
+        let bus = Bus::start();
+        let (_server, mut messages) = bus.serve().await;
+        let client = bus.connect().await;
+        call(&client, "Activate", &("",)).await.unwrap();
+        assert!(matches!(
+            messages.try_next(),
+            Ok(Some(Message::Activate(None, None)))
+        ));
+        call(&client, "ShowPage", &("dock", "token")).await.unwrap();
+        assert!(matches!(
+            messages.try_next(),
+            Ok(Some(Message::Activate(Some(Request::Page(Page::Dock)), Some(token)))) if token == "token"
+        ));
+    }
+
+    #[tokio::test]
+    async fn delivers_signals() {
+        let bus = Bus::start();
+        let (server, _messages) = bus.serve().await;
+        let client = bus.connect().await;
+        let rule = MatchRule::builder()
+            .msg_type(message::Type::Signal)
+            .interface(App::APP_ID)
+            .unwrap()
+            .member("LayoutApplied")
+            .unwrap()
+            .build();
+        let mut signals = MessageStream::for_match_rule(rule, &client, None)
+            .await
+            .unwrap();
+
+        let id = Uuid::new_v4();
+        Signal::LayoutApplied(id).emit(&server).await.unwrap();
+
+        let signal = signals.next().await.unwrap().unwrap();
+        assert_eq!(
+            signal.header().sender().map(|name| name.as_str()),
+            server.unique_name().map(|name| name.as_str())
+        );
+        assert_eq!(
+            signal.body().deserialize::<String>().unwrap(),
+            id.to_string()
+        );
+    }
+
+    #[tokio::test]
+    async fn forwarding_waits_for_the_object() {
+        let bus = Bus::start();
+        // The name is claimed before the window starts serving the object.
+        let server = zbus::connection::Builder::address(bus.address.as_str())
+            .unwrap()
+            .name(App::APP_ID)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+        let address = bus.address.clone();
+        let launch = std::thread::spawn(move || {
+            let connection = zbus::blocking::connection::Builder::address(address.as_str())
+                .unwrap()
+                .build()
+                .unwrap();
+            forward(&connection, Some(&Request::Page(Page::Dock)))
+        });
+
+        tokio::time::sleep(SERVE_RETRY_DELAY * 3).await;
+        let (output, mut messages) = mpsc::channel(4);
+        server
+            .object_server()
+            .at(PATH, Service { output })
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            messages.next().await,
+            Some(Message::Activate(Some(Request::Page(Page::Dock)), _))
+        ));
+        launch.join().unwrap().unwrap();
+    }
+}
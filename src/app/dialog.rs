@@ -1,8 +1,11 @@
+use std::path::PathBuf;
+
 use cosmic::{Element, widget};
 
 use crate::app::App;
 use crate::app::message::Message;
-use crate::app::pages::layouts::dialog::{CreateLayoutDialog, PanelType};
+use crate::app::pages::layouts::dialog::CreateLayoutDialog;
+use crate::app::pages::shortcuts::dialog::{REBIND, ResolveShortcutsDialog};
 
 use super::Cosmic;
 use crate::fl;
@@ -12,6 +15,14 @@ pub enum DialogPage {
     SaveCurrentColorScheme(String),
     CreateSnapshot(String),
     CreateLayout(CreateLayoutDialog),
+    CreatePreset(String),
+    ResolveShortcuts(ResolveShortcutsDialog),
+    CreateShortcutGroup(String),
+    CreateProfile(String),
+    DeletePanel(String),
+    DeleteIncompatibleSnapshot(PathBuf),
+    SwitchProfile(usize),
+    DeleteProfile(usize),
 }
 
 impl Cosmic {
@@ -59,20 +70,115 @@ impl Cosmic {
                         })
                         .on_submit(|_| Message::DialogComplete),
                 ),
+            DialogPage::CreatePreset(name) => widget::dialog()
+                .title(fl!("save-current-preset"))
+                .body(fl!("save-current-preset-description"))
+                .primary_action(
+                    widget::button::suggested(fl!("create"))
+                        .on_press_maybe((!name.is_empty()).then_some(Message::DialogComplete)),
+                )
+                .secondary_action(
+                    widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                )
+                .control(
+                    widget::text_input(fl!("preset-name"), name.as_str())
+                        .id(app.cosmic.dialog_text_input.clone())
+                        .on_input(move |name| Message::DialogUpdate(DialogPage::CreatePreset(name)))
+                        .on_submit(|_| Message::DialogComplete),
+                ),
+            DialogPage::CreateProfile(name) => {
+                widget::dialog()
+                    .title(fl!("save-profile"))
+                    .body(fl!("save-profile-description"))
+                    .primary_action(widget::button::suggested(fl!("create")).on_press_maybe(
+                        (!name.trim().is_empty()).then_some(Message::DialogComplete),
+                    ))
+                    .secondary_action(
+                        widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                    )
+                    .control(
+                        widget::text_input(fl!("profile-name"), name.as_str())
+                            .id(app.cosmic.dialog_text_input.clone())
+                            .on_input(move |name| {
+                                Message::DialogUpdate(DialogPage::CreateProfile(name))
+                            })
+                            .on_submit(|_| Message::DialogComplete),
+                    )
+            }
+            DialogPage::DeletePanel(name) => widget::dialog()
+                .title(fl!("delete-panel", name = name.as_str()))
+                .body(fl!("delete-panel-description"))
+                .primary_action(
+                    widget::button::destructive(fl!("delete")).on_press(Message::DialogComplete),
+                )
+                .secondary_action(
+                    widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                ),
+            DialogPage::DeleteIncompatibleSnapshot(path) => widget::dialog()
+                .title(fl!("delete-incompatible-snapshot"))
+                .body(fl!(
+                    "delete-incompatible-snapshot-description",
+                    name = path
+                        .file_name()
+                        .map_or_else(String::new, |name| name.to_string_lossy().into_owned())
+                ))
+                .primary_action(
+                    widget::button::destructive(fl!("delete")).on_press(Message::DialogComplete),
+                )
+                .secondary_action(
+                    widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                ),
+            DialogPage::SwitchProfile(index) => widget::dialog()
+                .title(fl!("switch-profile", name = profile_name(app, *index)))
+                .body(fl!("switch-profile-description"))
+                .primary_action(
+                    widget::button::suggested(fl!("switch")).on_press(Message::DialogComplete),
+                )
+                .secondary_action(
+                    widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                ),
+            DialogPage::DeleteProfile(index) => widget::dialog()
+                .title(fl!("delete-profile", name = profile_name(app, *index)))
+                .body(fl!("delete-profile-description"))
+                .primary_action(
+                    widget::button::destructive(fl!("delete")).on_press(Message::DialogComplete),
+                )
+                .secondary_action(
+                    widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                ),
+            DialogPage::CreateShortcutGroup(name) => {
+                widget::dialog()
+                    .title(fl!("save-as-group"))
+                    .body(fl!("save-as-group-description"))
+                    .primary_action(widget::button::suggested(fl!("create")).on_press_maybe(
+                        (!name.trim().is_empty()).then_some(Message::DialogComplete),
+                    ))
+                    .secondary_action(
+                        widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                    )
+                    .control(
+                        widget::text_input(fl!("shortcut-group-name"), name.as_str())
+                            .id(app.cosmic.dialog_text_input.clone())
+                            .on_input(move |name| {
+                                Message::DialogUpdate(DialogPage::CreateShortcutGroup(name))
+                            })
+                            .on_submit(|_| Message::DialogComplete),
+                    )
+            }
             DialogPage::CreateLayout(dialog) => {
                 let CreateLayoutDialog {
                     name,
-                    preview,
+                    schema,
                     error,
                 } = dialog;
-                let preview_view = preview.view::<Message>(&spacing, 130);
+                let preview_view = dialog.preview().view::<Message>(&spacing, 130);
 
                 let name_input =
                     widget::text_input(fl!("layout-name"), name)
                         .id(app.cosmic.dialog_text_input.clone())
                         .on_input(move |name| {
                             Message::DialogUpdate(DialogPage::CreateLayout(
-                                CreateLayoutDialog::new(name.clone(), *preview, error.clone()),
+                                CreateLayoutDialog::new(name, schema.clone(), error.clone()),
                             ))
                         })
                         .on_submit(|_| Message::DialogComplete);
@@ -86,7 +192,7 @@ impl Cosmic {
                             Message::DialogUpdate(DialogPage::CreateLayout(
                                 CreateLayoutDialog::new(
                                     name.clone(),
-                                    *preview,
+                                    schema.clone(),
                                     Some(fl!("layout-name-empty")),
                                 ),
                             ))
@@ -107,28 +213,110 @@ impl Cosmic {
                                         widget::text::caption(error.to_string())
                                             .class(cosmic::style::Text::Accent)
                                     }))
-                                    .push(
-                                        widget::scrollable(
-                                            widget::column()
-                                                .push(dialog.section(
-                                                    PanelType::Panel,
-                                                    &app.layouts.panel_model,
-                                                ))
-                                                .push(dialog.section(
-                                                    PanelType::Dock,
-                                                    &app.layouts.dock_model,
-                                                )),
-                                        )
-                                        .height(300),
-                                    )
                                     .padding(spacing.space_s)
                                     .spacing(spacing.space_m),
                             )
                             .spacing(spacing.space_m),
                     )
             }
+            DialogPage::ResolveShortcuts(dialog) => {
+                let conflicts = dialog.conflicts.iter().enumerate().fold(
+                    widget::settings::section().title(fl!("conflicts")),
+                    |section, (index, conflict)| {
+                        let on_choice = dialog.clone();
+                        let on_rebind = dialog.clone();
+                        let error = (conflict.choice == REBIND)
+                            .then(|| dialog.rebind(index).err())
+                            .flatten();
+                        section.add(
+                            widget::settings::item::builder(conflict.binding.to_string())
+                                .description(dialog.current(index))
+                                .control(
+                                    widget::column()
+                                        .push(widget::dropdown(
+                                            &dialog.choices,
+                                            Some(conflict.choice),
+                                            move |choice| {
+                                                Message::DialogUpdate(DialogPage::ResolveShortcuts(
+                                                    on_choice.with_choice(index, choice),
+                                                ))
+                                            },
+                                        ))
+                                        .push_maybe((conflict.choice == REBIND).then(|| {
+                                            widget::text_input(
+                                                fl!("binding-placeholder"),
+                                                conflict.rebind.as_str(),
+                                            )
+                                            .on_input(
+                                                move |text| {
+                                                    Message::DialogUpdate(
+                                                        DialogPage::ResolveShortcuts(
+                                                            on_rebind.with_rebind(index, text),
+                                                        ),
+                                                    )
+                                                },
+                                            )
+                                        }))
+                                        .push_maybe(error.map(|error| {
+                                            widget::text::caption(error)
+                                                .class(cosmic::style::Text::Accent)
+                                        }))
+                                        .spacing(spacing.space_xxs),
+                                ),
+                        )
+                    },
+                );
+
+                let summary = dialog.summary();
+                let summary = if summary.is_empty() {
+                    widget::column().push(widget::text::body(fl!("no-shortcut-changes")))
+                } else {
+                    widget::column::with_children(
+                        summary
+                            .into_iter()
+                            .map(|change| widget::text::body(change).into())
+                            .collect(),
+                    )
+                };
+
+                widget::dialog()
+                    .width(700)
+                    .title(fl!(
+                        "resolve-shortcuts",
+                        scheme = dialog.scheme.name.as_str()
+                    ))
+                    .body(fl!("resolve-shortcuts-description"))
+                    .primary_action(
+                        widget::button::suggested(fl!("apply-scheme")).on_press_maybe(
+                            dialog
+                                .resolutions()
+                                .is_some()
+                                .then_some(Message::DialogComplete),
+                        ),
+                    )
+                    .secondary_action(
+                        widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                    )
+                    .control(
+                        widget::scrollable(
+                            widget::column()
+                                .push(conflicts)
+                                .push(widget::text::heading(fl!("changes")))
+                                .push(summary.spacing(spacing.space_xxs))
+                                .spacing(spacing.space_m),
+                        )
+                        .height(400),
+                    )
+            }
         };
 
         Some(dialog.into())
     }
 }
+
+fn profile_name(app: &App, index: usize) -> &str {
+    app.config
+        .profiles
+        .get(index)
+        .map_or("", |profile| profile.name.as_str())
+}
@@ -1,9 +1,14 @@
 use cosmic::cosmic_config::Config;
 
-use crate::app::core::config::TweaksConfig;
+use crate::app::core::{cli::Request, config::TweaksConfig};
 
 #[derive(Clone, Debug)]
 pub struct Flags {
     pub handler: Config,
     pub config: TweaksConfig,
+    /// What the launch asked for, carried out once the window is open.
+    pub request: Option<Request>,
+    /// The session bus connection holding the app's name, served on once the
+    /// window is open.
+    pub dbus: Option<zbus::Connection>,
 }
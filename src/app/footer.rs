@@ -3,7 +3,7 @@ use cosmic::{
     widget::{self},
 };
 
-use crate::app::{message::Message, pages::layouts::dialog::CreateLayoutDialog};
+use crate::app::message::Message;
 use crate::app::{page::Page, pages};
 use crate::{
     app::{App, dialog::DialogPage},
@@ -11,7 +11,6 @@ use crate::{
 };
 
 use super::Cosmic;
-use crate::app::pages::layouts::preview::LayoutPreview;
 use crate::fl;
 
 impl Cosmic {
@@ -23,38 +22,130 @@ impl Cosmic {
                 .color_schemes
                 .footer()
                 .map(|elem| elem.map(|message| Message::ColorSchemes(Box::new(message)))),
+            Page::Layouts if app.layouts.editor.is_some() => Some(
+                widget::row()
+                    .push(widget::horizontal_space())
+                    .push(
+                        widget::button::standard(fl!("cancel"))
+                            .on_press(Message::Layouts(pages::layouts::Message::CancelEdit)),
+                    )
+                    .push(
+                        widget::button::suggested(fl!("save"))
+                            .trailing_icon(icon_handle!("document-save-symbolic", 16))
+                            .spacing(spacing.space_xs)
+                            .on_press(Message::Layouts(pages::layouts::Message::SaveEdit)),
+                    )
+                    .spacing(spacing.space_xxs)
+                    .apply(widget::container)
+                    .class(cosmic::style::Container::Card)
+                    .padding(spacing.space_xxs)
+                    .into(),
+            ),
+            Page::Layouts if app.layouts.active_tab() == pages::layouts::Tab::Available => Some(
+                widget::row()
+                    .push(widget::horizontal_space())
+                    .push(
+                        widget::button::standard(fl!("refresh")).on_press(Message::Layouts(
+                            pages::layouts::Message::FetchAvailableLayouts,
+                        )),
+                    )
+                    .spacing(spacing.space_xxs)
+                    .apply(widget::container)
+                    .class(cosmic::style::Container::Card)
+                    .padding(spacing.space_xxs)
+                    .into(),
+            ),
+            Page::Layouts if app.layouts.active_tab() == pages::layouts::Tab::Presets => Some(
+                widget::row()
+                    .push(widget::horizontal_space())
+                    .push(
+                        widget::button::standard(fl!("save-current-preset"))
+                            .trailing_icon(icon_handle!("arrow-into-box-symbolic", 16))
+                            .spacing(spacing.space_xs)
+                            .on_press(Message::Layouts(pages::layouts::Message::SaveCurrentPreset)),
+                    )
+                    .push_maybe(app.layouts.selected_preset.as_ref().map(|_| {
+                        widget::button::standard(fl!("apply-preset"))
+                            .trailing_icon(icon_handle!("checkmark-symbolic", 16))
+                            .spacing(spacing.space_xs)
+                            .on_press(Message::Layouts(pages::layouts::Message::ApplyPreset))
+                    }))
+                    .push_maybe(
+                        app.layouts
+                            .selected_preset
+                            .as_ref()
+                            .filter(|selected| selected.custom)
+                            .map(|_| {
+                                widget::button::standard(fl!("delete-preset"))
+                                    .trailing_icon(icon_handle!("recycling-bin-symbolic", 16))
+                                    .spacing(spacing.space_xs)
+                                    .on_press(Message::Layouts(
+                                        pages::layouts::Message::DeletePreset,
+                                    ))
+                            }),
+                    )
+                    .spacing(spacing.space_xxs)
+                    .apply(widget::container)
+                    .class(cosmic::style::Container::Card)
+                    .padding(spacing.space_xxs)
+                    .into(),
+            ),
             Page::Layouts => Some(
                 widget::row()
                     .push(widget::horizontal_space())
+                    .push(
+                        widget::button::standard(fl!("import-layout"))
+                            .trailing_icon(icon_handle!("folder-download-symbolic", 16))
+                            .spacing(spacing.space_xs)
+                            .on_press(Message::Layouts(pages::layouts::Message::StartImport)),
+                    )
+                    .push_maybe(app.layouts.selected_layout.as_ref().map(|_| {
+                        widget::button::standard(fl!("export-layout"))
+                            .trailing_icon(icon_handle!("document-save-symbolic", 16))
+                            .spacing(spacing.space_xs)
+                            .on_press(Message::Layouts(pages::layouts::Message::StartExport))
+                    }))
+                    .push(
+                        widget::button::standard(fl!("new-layout"))
+                            .trailing_icon(icon_handle!("list-add-symbolic", 16))
+                            .spacing(spacing.space_xs)
+                            .on_press(Message::Layouts(pages::layouts::Message::NewLayout)),
+                    )
                     .push(
                         widget::button::standard(fl!("save-current-layout"))
                             .trailing_icon(icon_handle!("arrow-into-box-symbolic", 16))
                             .spacing(spacing.space_xs)
-                            .on_press(Message::ToggleDialogPage(DialogPage::CreateLayout(
-                                CreateLayoutDialog::new(
-                                    String::new(),
-                                    LayoutPreview::default(),
-                                    None,
-                                ),
-                            ))),
+                            .on_press(Message::Layouts(pages::layouts::Message::SaveCurrentLayout)),
                     )
+                    .push_maybe(app.layouts.selected_layout.as_ref().map(|selected| {
+                        if selected.custom {
+                            widget::button::standard(fl!("edit-layout"))
+                                .trailing_icon(icon_handle!("edit-symbolic", 16))
+                                .spacing(spacing.space_xs)
+                                .on_press(Message::Layouts(pages::layouts::Message::Edit))
+                        } else {
+                            widget::button::standard(fl!("duplicate-layout"))
+                                .trailing_icon(icon_handle!("edit-symbolic", 16))
+                                .spacing(spacing.space_xs)
+                                .on_press(Message::Layouts(pages::layouts::Message::Duplicate))
+                        }
+                    }))
                     .push_maybe(app.layouts.selected_layout.as_ref().map(|_| {
                         widget::button::standard(fl!("apply-layout"))
                             .trailing_icon(icon_handle!("checkmark-symbolic", 16))
                             .spacing(spacing.space_xs)
                             .on_press(Message::Layouts(pages::layouts::Message::Apply))
                     }))
-                    .push_maybe(app.layouts.selected_layout.as_ref().and_then(|selected| {
-                        if selected.custom {
-                            Some(
-                                widget::button::standard(fl!("delete-layout"))
-                                    .trailing_icon(icon_handle!("recycling-bin-symbolic", 16))
-                                    .spacing(spacing.space_xs)
-                                    .on_press(Message::Layouts(pages::layouts::Message::Delete)),
-                            )
+                    .push_maybe(app.layouts.selected_layout.as_ref().map(|selected| {
+                        let label = if selected.custom {
+                            fl!("delete-layout")
                         } else {
-                            None
-                        }
+                            fl!("hide-layout")
+                        };
+                        widget::button::standard(label)
+                            .trailing_icon(icon_handle!("recycling-bin-symbolic", 16))
+                            .spacing(spacing.space_xs)
+                            .on_press(Message::Layouts(pages::layouts::Message::Delete))
                     }))
                     .spacing(spacing.space_xxs)
                     .apply(widget::container)
@@ -62,6 +153,19 @@ impl Cosmic {
                     .padding(spacing.space_xxs)
                     .into(),
             ),
+            Page::Applets => Some(
+                widget::row()
+                    .push(widget::horizontal_space())
+                    .push(
+                        widget::button::standard(fl!("refresh"))
+                            .on_press(Message::Applets(pages::applets::Message::Refresh)),
+                    )
+                    .spacing(spacing.space_xxs)
+                    .apply(widget::container)
+                    .class(cosmic::style::Container::Card)
+                    .padding(spacing.space_xxs)
+                    .into(),
+            ),
             Page::Snapshots => Some(
                 widget::row()
                     .push(widget::horizontal_space())
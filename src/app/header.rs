@@ -12,25 +12,72 @@ use crate::{fl, icon_handle};
 
 impl Cosmic {
     pub fn header_start<'a>(app: &'a App) -> Vec<Element<'a, Message>> {
-        let menu_bar = menu::bar(vec![menu::Tree::with_children(
-            RcElementWrapper::new(menu::root(fl!("view")).into()),
-            menu::items(
-                &app.cosmic.key_binds,
-                vec![
-                    menu::Item::Button(
-                        fl!("settings"),
-                        Some(icon_handle!("settings-symbolic", 14)),
-                        TweaksAction::Settings,
-                    ),
-                    menu::Item::Divider,
-                    menu::Item::Button(
-                        fl!("about"),
-                        Some(icon_handle!("info-outline-symbolic", 14)),
-                        TweaksAction::About,
-                    ),
-                ],
+        let profile = match app.config.active_profile() {
+            Some(profile) if app.cosmic.profile_drift => {
+                fl!("profile-modified", name = profile.name.as_str())
+            }
+            Some(profile) => profile.name.clone(),
+            None => fl!("profiles"),
+        };
+        let mut profiles: Vec<menu::Item<TweaksAction, String>> = app
+            .config
+            .profiles
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                menu::Item::CheckBox(
+                    item.name.clone(),
+                    None,
+                    app.config.active_profile == Some(item.id),
+                    TweaksAction::SwitchProfile(index),
+                )
+            })
+            .collect();
+        if !profiles.is_empty() {
+            profiles.push(menu::Item::Divider);
+            profiles.push(menu::Item::Button(
+                fl!("next-profile"),
+                None,
+                TweaksAction::NextProfile,
+            ));
+        }
+        profiles.push(menu::Item::Button(
+            fl!("save-profile"),
+            Some(icon_handle!("document-save-symbolic", 14)),
+            TweaksAction::SaveProfile,
+        ));
+
+        let menu_bar = menu::bar(vec![
+            menu::Tree::with_children(
+                RcElementWrapper::new(menu::root(fl!("view")).into()),
+                menu::items(
+                    &app.cosmic.key_binds,
+                    vec![
+                        menu::Item::Button(
+                            fl!("search-settings"),
+                            Some(icon_handle!("search-global-symbolic", 14)),
+                            TweaksAction::Search,
+                        ),
+                        menu::Item::Button(
+                            fl!("settings"),
+                            Some(icon_handle!("settings-symbolic", 14)),
+                            TweaksAction::Settings,
+                        ),
+                        menu::Item::Button(fl!("error-log"), None, TweaksAction::ErrorLog),
+                        menu::Item::Divider,
+                        menu::Item::Button(
+                            fl!("about"),
+                            Some(icon_handle!("info-outline-symbolic", 14)),
+                            TweaksAction::About,
+                        ),
+                    ],
+                ),
+            ),
+            menu::Tree::with_children(
+                RcElementWrapper::new(menu::root(profile).into()),
+                menu::items(&app.cosmic.key_binds, profiles),
             ),
-        )])
+        ])
         .item_height(ItemHeight::Dynamic(40))
         .item_width(ItemWidth::Uniform(240))
         .spacing(4.0);
@@ -50,6 +97,12 @@ impl Cosmic {
                 .into_iter()
                 .map(|e| e.map(|m| Message::ColorSchemes(Box::new(m))))
                 .collect(),
+            Page::Layouts => app
+                .layouts
+                .header_end()
+                .into_iter()
+                .map(|e| e.map(Message::Layouts))
+                .collect(),
             _ => vec![],
         }
     }
@@ -11,13 +11,15 @@ use crate::app::App;
 use crate::app::flags::Flags;
 use crate::app::message::Message;
 use crate::app::page::Page;
+use crate::app::report::Report;
+use crate::app::search::Search;
 use crate::app::{context::ContextPage, pages::snapshots::config::SnapshotKind};
 
 use super::Cosmic;
 use crate::app::core::key_bindings::KeyBindings;
 use crate::app::pages::{
-    self, color_schemes::ColorSchemes, dock::Dock, layouts::Layouts, panel::Panel,
-    shortcuts::ShortcutsPage, snapshots::Snapshots,
+    self, applets::Applets, color_schemes::ColorSchemes, dock::Dock, layouts::Layouts,
+    panel::Panel, panels::Panels, shortcuts::ShortcutsPage, snapshots::Snapshots,
 };
 use crate::fl;
 
@@ -60,6 +62,15 @@ impl Cosmic {
 
         tasks.push(task.map(|m| cosmic::Action::App(Message::ColorSchemes(Box::new(m)))));
 
+        let (layouts, task) = Layouts::new(
+            flags.config.layout_catalog_url.clone(),
+            flags.config.hidden_layouts.clone(),
+        );
+
+        tasks.push(task.map(cosmic::Action::App));
+
+        let shortcuts = ShortcutsPage::new(flags.config.shortcut_schemes.clone());
+
         let mut app = App {
             cosmic: Cosmic {
                 core,
@@ -71,15 +82,23 @@ impl Cosmic {
                 modifiers: Modifiers::empty(),
                 context_page: ContextPage::About,
                 app_themes: vec![fl!("match-desktop"), fl!("dark"), fl!("light")],
+                search: Search::default(),
+                profile_drift: false,
+                profile_drift_check: Default::default(),
+                dbus: flags.dbus,
+                toasts: widget::toaster::Toasts::new(Message::CloseToast),
+                errors: Vec::new(),
             },
             handler: flags.handler,
             config: flags.config,
             color_schemes,
-            layouts: Layouts::default(),
+            layouts,
             dock: Dock::default(),
             panel: Panel::default(),
+            panels: Panels::default(),
+            applets: Applets::default(),
             snapshots: Snapshots::default(),
-            shortcuts: ShortcutsPage::new(),
+            shortcuts,
         };
 
         tasks.push(app.update(Message::Snapshots(
@@ -90,14 +109,19 @@ impl Cosmic {
         )));
 
         match pages::layouts::config::Layout::list() {
-            Ok(list) => {
-                tasks.push(app.update(Message::Layouts(pages::layouts::Message::LoadLayouts(list))))
-            }
-            Err(error) => log::error!("Failed to load layouts: {}", error),
+            Ok((list, incompatible)) => tasks.push(app.update(Message::Layouts(
+                pages::layouts::Message::LoadLayouts(list, incompatible),
+            ))),
+            Err(error) => tasks.push(app.report(Report::new(fl!("failed-load-layouts"), error))),
         }
 
+        tasks.push(app.check_profile_drift());
         tasks.push(app.set_window_title(fl!("app-title")));
 
+        if let Some(request) = flags.request {
+            tasks.push(app.update(Message::Activate(Some(request), None)));
+        }
+
         (app, Task::batch(tasks))
     }
 }
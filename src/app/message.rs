@@ -1,25 +1,28 @@
 use crate::app::{
-    core::config::TweaksConfig,
-    pages::{self, layouts::preview::LayoutPreview},
+    core::{cli::Request, config::TweaksConfig},
+    pages::{self, panel::CosmicPanel, panel::config::CosmicPanelButtonConfig},
 };
 
-use super::{context::ContextPage, dialog::DialogPage};
+use super::{context::ContextPage, dialog::DialogPage, report::Report};
 use cosmic::{
-    cosmic_theme::ThemeMode,
+    cosmic_theme::{ThemeBuilder, ThemeMode},
     iced::keyboard::{Key, Modifiers},
-    widget,
+    widget::toaster::ToastId,
 };
+use cosmic_panel_config::CosmicPanelConfig;
+use cosmic_settings_config::Shortcuts;
+use uuid::Uuid;
 
 #[derive(Debug, Clone)]
 pub enum Message {
     Dock(pages::dock::Message),
     Panel(pages::panel::Message),
+    Panels(pages::panels::Message),
+    Applets(pages::applets::Message),
     Layouts(pages::layouts::Message),
     Shortcuts(pages::shortcuts::Message),
     Snapshots(pages::snapshots::Message),
     ColorSchemes(Box<pages::color_schemes::Message>),
-    UpdatePanelLayoutPosition(widget::segmented_button::Entity, String, LayoutPreview),
-    UpdateDockLayoutPosition(widget::segmented_button::Entity, String, LayoutPreview),
     DialogUpdate(DialogPage),
     DialogComplete,
     DialogCancel,
@@ -30,12 +33,51 @@ pub enum Message {
     Key(Modifiers, Key),
     Modifiers(Modifiers),
     SystemThemeModeChange(ThemeMode),
+    SystemThemeChange(bool, Box<ThemeBuilder>),
+    PanelConfigChange(Box<CosmicPanelConfig>),
+    PanelEntriesChange(CosmicPanel),
+    PanelButtonsChange(CosmicPanelButtonConfig),
+    ShortcutsChange(Shortcuts),
     Open(String),
+    Search(String),
+    SearchSelect(usize),
     Settings(SettingsMessage),
+    Profile(ProfileMessage),
+    /// Whether the desktop drifted from the active profile, by the check
+    /// that found out.
+    ProfileDrift(u64, bool),
+    /// Raises the window and carries out what another launch asked for, with
+    /// the activation token it was started with.
+    Activate(Option<Request>, Option<String>),
+    Error(Report),
+    RetryError(usize),
+    ClearErrors,
+    ShowErrorLog,
+    CloseToast(ToastId),
 }
 
 #[derive(Debug, Clone)]
 pub enum SettingsMessage {
     AppTheme(usize),
+    LayoutCatalogUrl(String),
+    HiddenLayouts(Vec<Uuid>),
+    ShortcutSchemes(Vec<pages::shortcuts::scheme::AppliedScheme>),
+    /// A layout was applied, announced on the bus and then recorded.
+    AppliedLayout(Option<Uuid>),
+    /// Records the applied layout without announcing it, which is what a
+    /// failed write retries.
+    SaveAppliedLayout(Option<Uuid>),
     ConfigUpdate(TweaksConfig),
 }
+
+/// Profiles are addressed by their index in `TweaksConfig::profiles`.
+#[derive(Debug, Clone)]
+pub enum ProfileMessage {
+    Switch(usize),
+    Next,
+    Save(String),
+    Update(usize),
+    /// Asks before deleting.
+    Delete(usize),
+    DeleteConfirmed(usize),
+}
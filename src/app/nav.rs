@@ -14,6 +14,7 @@ use super::Cosmic;
 impl Cosmic {
     pub fn on_nav_select(app: &mut App, id: widget::nav_bar::Id) -> app::Task<Message> {
         app.cosmic.nav_model.activate(id);
+        app.clear_highlights();
 
         let title = if let Some(page) = app.cosmic.nav_model.data::<Page>(id) {
             format!("{} - {}", page.title(), fl!("app-title"))
@@ -23,4 +24,16 @@ impl Cosmic {
 
         Task::batch(vec![app.set_window_title(title)])
     }
+
+    /// Opens a page by what it shows rather than by its nav item.
+    pub fn open_page(app: &mut App, page: Page) -> app::Task<Message> {
+        let nav_model = &app.cosmic.nav_model;
+        let id = nav_model
+            .iter()
+            .find(|id| nav_model.data::<Page>(*id) == Some(&page));
+        match id {
+            Some(id) => Self::on_nav_select(app, id),
+            None => Task::none(),
+        }
+    }
 }
@@ -8,6 +8,8 @@ pub enum Page {
     ColorSchemes,
     Dock,
     Panel,
+    Panels,
+    Applets,
     Layouts,
     Shortcuts,
     Snapshots,
@@ -25,17 +27,42 @@ impl Page {
             Self::ColorSchemes => fl!("color-schemes"),
             Self::Dock => fl!("dock"),
             Self::Panel => fl!("panel"),
+            Self::Panels => fl!("panels"),
+            Self::Applets => fl!("applets"),
             Self::Layouts => fl!("layouts"),
             Self::Shortcuts => fl!("shortcuts"),
             Self::Snapshots => fl!("snapshots"),
         }
     }
 
+    /// The name of the page on the command line, such as `color-schemes`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::ColorSchemes => "color-schemes",
+            Self::Dock => "dock",
+            Self::Panel => "panel",
+            Self::Panels => "panels",
+            Self::Applets => "applets",
+            Self::Layouts => "layouts",
+            Self::Shortcuts => "shortcuts",
+            Self::Snapshots => "snapshots",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::all()
+            .iter()
+            .copied()
+            .find(|page| page.name().eq_ignore_ascii_case(name))
+    }
+
     pub fn icon(&self) -> Icon {
         match self {
             Self::ColorSchemes => icon!("dark-mode-symbolic", 18),
             Self::Dock => icon!("dock-bottom-symbolic", 18),
             Self::Panel => icon!("dock-top-symbolic", 18),
+            Self::Panels => icon!("tabs-stack-symbolic", 18),
+            Self::Applets => icon!("selection-mode-symbolic", 18),
             Self::Layouts => icon!("view-coverflow-symbolic", 18),
             Self::Shortcuts => icon!("keyboard-symbolic", 18),
             Self::Snapshots => icon!("snapshots-symbolic", 18),
@@ -47,6 +74,8 @@ impl Page {
             Self::ColorSchemes,
             Self::Dock,
             Self::Panel,
+            Self::Panels,
+            Self::Applets,
             Self::Layouts,
             Self::Shortcuts,
             Self::Snapshots,
@@ -0,0 +1,111 @@
+use std::path::PathBuf;
+
+use crate::localize::LANGUAGE_SORTER;
+
+/// A panel applet installed on the system.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Applet {
+    /// The desktop entry id, which is also the id panels list the applet by.
+    pub id: String,
+    pub name: String,
+    pub comment: Option<String>,
+    pub icon: Option<String>,
+}
+
+/// The `applications` dirs of the XDG data dirs, the user's own first.
+fn application_dirs() -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = dirs::data_dir()
+        .map(|data| data.join("applications"))
+        .into_iter()
+        .collect();
+
+    let data_dirs = std::env::var("XDG_DATA_DIRS")
+        .ok()
+        .filter(|data_dirs| !data_dirs.is_empty())
+        .unwrap_or_else(|| "/usr/local/share:/usr/share".to_string());
+    paths.extend(
+        data_dirs
+            .split(':')
+            .filter(|dir| !dir.is_empty())
+            .map(|dir| PathBuf::from(dir).join("applications")),
+    );
+
+    paths
+}
+
+/// Finds the applets advertised with `X-CosmicApplet=true` in desktop entries.
+///
+/// Entries in earlier dirs shadow later ones with the same id.
+pub fn discover() -> Vec<Applet> {
+    let mut applets: Vec<Applet> = Vec::new();
+
+    for dir in application_dirs() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("desktop") {
+                continue;
+            }
+            let Some(id) = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+            else {
+                continue;
+            };
+            if applets.iter().any(|applet| applet.id == id) {
+                continue;
+            }
+            match std::fs::read_to_string(&path) {
+                Ok(contents) => applets.extend(parse(id, &contents)),
+                Err(e) => log::warn!("Failed to read {}: {e}", path.display()),
+            }
+        }
+    }
+
+    applets.sort_by(|a, b| LANGUAGE_SORTER.compare(&a.name, &b.name));
+    applets
+}
+
+/// Reads the `[Desktop Entry]` group, returning `None` for anything but an applet.
+///
+/// Applets usually set `NoDisplay` to stay out of launchers, so only `Hidden`,
+/// which marks a deleted entry, excludes one.
+fn parse(id: String, contents: &str) -> Option<Applet> {
+    let mut in_entry = false;
+    let mut is_applet = false;
+    let mut hidden = false;
+    let mut name = None;
+    let mut comment = None;
+    let mut icon = None;
+
+    for line in contents.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_entry = line == "[Desktop Entry]";
+            continue;
+        }
+        if !in_entry {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.trim();
+        match key.trim() {
+            "Name" => name = Some(value.to_string()),
+            "Comment" => comment = Some(value.to_string()),
+            "Icon" => icon = Some(value.to_string()),
+            "X-CosmicApplet" => is_applet = value == "true",
+            "Hidden" => hidden = value == "true",
+            _ => {}
+        }
+    }
+
+    (is_applet && !hidden).then(|| Applet {
+        name: name.unwrap_or_else(|| id.clone()),
+        id,
+        comment,
+        icon,
+    })
+}
@@ -0,0 +1,331 @@
+use cosmic::{
+    Element, Task,
+    cosmic_config::{self, Config, ConfigSet, CosmicConfigEntry},
+    iced::{Alignment, Length},
+    widget,
+};
+use cosmic_panel_config::CosmicPanelConfig;
+
+use super::layouts::editor::{Direction, Section, applet_name, applets, applets_mut};
+use super::panel::CosmicPanel;
+use crate::app::report::Report;
+use crate::{fl, icon};
+use discovery::Applet;
+
+pub mod discovery;
+
+const SECTIONS: [Section; 3] = [Section::Start, Section::Center, Section::End];
+
+/// Lists the installed applets and arranges them in the sections of each panel.
+pub struct Applets {
+    installed: Vec<Applet>,
+    panels: Vec<PanelEntry>,
+    panel_names: Vec<String>,
+    section_names: Vec<String>,
+    selected: usize,
+    add_to: usize,
+}
+
+struct PanelEntry {
+    helper: Config,
+    config: CosmicPanelConfig,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    SelectPanel(usize),
+    SetAddSection(usize),
+    Add(String),
+    Remove(Section, usize),
+    Move(Section, usize, Direction),
+    MoveToSection(Section, usize, Section),
+    Refresh,
+}
+
+impl Default for Applets {
+    fn default() -> Self {
+        let mut applets = Self {
+            installed: Vec::new(),
+            panels: Vec::new(),
+            panel_names: Vec::new(),
+            section_names: SECTIONS.iter().map(Section::title).collect(),
+            selected: 0,
+            add_to: 2,
+        };
+        applets.reload();
+        applets
+    }
+}
+
+impl Applets {
+    /// Reads the installed applets and the config of every panel entry.
+    fn reload(&mut self) {
+        self.installed = discovery::discover();
+        self.reload_panels();
+    }
+
+    /// Takes in the config of a panel after it changed on disk.
+    pub fn sync_config(&mut self, config: CosmicPanelConfig) {
+        if let Some(panel) = self
+            .panels
+            .iter_mut()
+            .find(|panel| panel.config.name == config.name)
+        {
+            panel.config = config;
+        }
+    }
+
+    /// Reads the config of every panel entry.
+    pub fn reload_panels(&mut self) {
+        let entries = match cosmic_config::Config::new("com.system76.CosmicPanel", 1) {
+            Ok(helper) => {
+                CosmicPanel::get_entry(&helper)
+                    .unwrap_or_else(|(errs, config)| {
+                        log::error!("errors loading config: {:?}", errs);
+                        config
+                    })
+                    .entries
+            }
+            Err(err) => {
+                log::error!("failed to create config handler: {}", err);
+                Vec::new()
+            }
+        };
+
+        self.panels = entries
+            .iter()
+            .filter_map(|name| {
+                let helper = CosmicPanelConfig::cosmic_config(name)
+                    .map_err(|err| log::error!("failed to load panel {name}: {err}"))
+                    .ok()?;
+                let config =
+                    CosmicPanelConfig::get_entry(&helper).unwrap_or_else(|(errs, config)| {
+                        log::error!("errors loading panel {name}: {:?}", errs);
+                        config
+                    });
+                Some(PanelEntry { helper, config })
+            })
+            .collect();
+        self.panel_names = self
+            .panels
+            .iter()
+            .map(|panel| panel.config.name.clone())
+            .collect();
+        self.selected = self.selected.min(self.panels.len().saturating_sub(1));
+    }
+
+    fn name<'a>(&'a self, id: &'a str) -> &'a str {
+        self.installed
+            .iter()
+            .find(|applet| applet.id == id)
+            .map(|applet| applet.name.as_str())
+            .unwrap_or_else(|| applet_name(id))
+    }
+
+    /// Where an applet is placed, such as "Panel · Center, Dock · End".
+    fn placement(&self, id: &str) -> Option<String> {
+        let places: Vec<String> = self
+            .panels
+            .iter()
+            .flat_map(|panel| {
+                SECTIONS.iter().filter_map(move |section| {
+                    applets(&panel.config, *section)
+                        .iter()
+                        .any(|applet| applet == id)
+                        .then(|| format!("{} · {}", panel.config.name, section.title()))
+                })
+            })
+            .collect();
+        (!places.is_empty()).then(|| places.join(", "))
+    }
+
+    /// Edits the applets of the selected panel and writes them back.
+    fn write(
+        &mut self,
+        edit: impl FnOnce(&mut CosmicPanelConfig),
+    ) -> Task<crate::app::message::Message> {
+        let Some(panel) = self.panels.get_mut(self.selected) else {
+            return Task::none();
+        };
+        let mut config = panel.config.clone();
+        edit(&mut config);
+
+        // Moving an applet between the wings and the center touches both keys, so
+        // they are written together to never drop or duplicate it.
+        let tx = panel.helper.transaction();
+        let written = tx
+            .set("plugins_wings", &config.plugins_wings)
+            .and_then(|_| tx.set("plugins_center", &config.plugins_center))
+            .and_then(|_| tx.commit());
+        if let Err(err) = written {
+            return Report::new(fl!("failed-save-applets"), err).task();
+        }
+        panel.config.plugins_wings = config.plugins_wings;
+        panel.config.plugins_center = config.plugins_center;
+        Task::none()
+    }
+
+    pub fn view<'a>(&'a self) -> Element<'a, Message> {
+        let spacing = cosmic::theme::spacing();
+
+        let panel = widget::settings::section()
+            .title(fl!("panel"))
+            .add(
+                widget::settings::item::builder(fl!("panel"))
+                    .icon(icon!("dock-top-symbolic", 18))
+                    .control(widget::dropdown(
+                        &self.panel_names,
+                        (!self.panels.is_empty()).then_some(self.selected),
+                        Message::SelectPanel,
+                    )),
+            )
+            .add(
+                widget::settings::item::builder(fl!("add-applets-to"))
+                    .icon(icon!("list-add-symbolic", 18))
+                    .control(widget::dropdown(
+                        &self.section_names,
+                        Some(self.add_to),
+                        Message::SetAddSection,
+                    )),
+            );
+
+        let sections = self.panels.get(self.selected).map(|panel| {
+            widget::row::with_children(
+                SECTIONS
+                    .iter()
+                    .map(|section| self.section(&panel.config, *section))
+                    .collect(),
+            )
+            .spacing(spacing.space_xs)
+        });
+
+        let selected = self.panels.get(self.selected).map(|panel| &panel.config);
+        let installed = self.installed.iter().fold(
+            widget::settings::section().title(fl!("installed-applets")),
+            |list, applet| {
+                let added = selected.is_some_and(|config| {
+                    SECTIONS
+                        .iter()
+                        .any(|section| applets(config, *section).contains(&applet.id))
+                });
+                let description = self
+                    .placement(&applet.id)
+                    .or_else(|| applet.comment.clone())
+                    .unwrap_or_default();
+                let mut item =
+                    widget::settings::item::builder(applet.name.clone()).description(description);
+                if let Some(icon) = &applet.icon {
+                    item = item.icon(widget::icon::from_name(icon.as_str()).size(18).icon());
+                }
+                list.add(
+                    item.control(widget::button::standard(fl!("add")).on_press_maybe(
+                        (!added && selected.is_some()).then(|| Message::Add(applet.id.clone())),
+                    )),
+                )
+            },
+        );
+
+        widget::scrollable(
+            widget::column()
+                .push(panel)
+                .push_maybe(sections)
+                .push(installed)
+                .spacing(spacing.space_m),
+        )
+        .into()
+    }
+
+    fn section<'a>(
+        &'a self,
+        config: &'a CosmicPanelConfig,
+        section: Section,
+    ) -> Element<'a, Message> {
+        let spacing = cosmic::theme::spacing();
+        let items = applets(config, section)
+            .iter()
+            .enumerate()
+            .map(|(index, id)| {
+                widget::row()
+                    .push(widget::text(self.name(id)).width(Length::Fill))
+                    .push(
+                        widget::button::icon(widget::icon::from_name("go-previous-symbolic"))
+                            .on_press_maybe(
+                                section
+                                    .previous()
+                                    .map(|to| Message::MoveToSection(section, index, to)),
+                            ),
+                    )
+                    .push(
+                        widget::button::icon(widget::icon::from_name("go-up-symbolic"))
+                            .on_press(Message::Move(section, index, Direction::Up)),
+                    )
+                    .push(
+                        widget::button::icon(widget::icon::from_name("go-down-symbolic"))
+                            .on_press(Message::Move(section, index, Direction::Down)),
+                    )
+                    .push(
+                        widget::button::icon(widget::icon::from_name("go-next-symbolic"))
+                            .on_press_maybe(
+                                section
+                                    .next()
+                                    .map(|to| Message::MoveToSection(section, index, to)),
+                            ),
+                    )
+                    .push(
+                        widget::button::icon(widget::icon::from_name("list-remove-symbolic"))
+                            .class(cosmic::style::Button::Destructive)
+                            .on_press(Message::Remove(section, index)),
+                    )
+                    .align_y(Alignment::Center)
+                    .into()
+            });
+
+        widget::column()
+            .push(widget::text::heading(section.title()))
+            .extend(items)
+            .spacing(spacing.space_xxs)
+            .width(Length::Fill)
+            .into()
+    }
+
+    pub fn update(&mut self, message: Message) -> Task<crate::app::message::Message> {
+        match message {
+            Message::SelectPanel(index) => self.selected = index,
+            Message::SetAddSection(index) => self.add_to = index,
+            Message::Add(id) => {
+                let section = SECTIONS[self.add_to.min(SECTIONS.len() - 1)];
+                return self.write(|config| applets_mut(config, section).push(id));
+            }
+            Message::Remove(section, index) => {
+                return self.write(|config| {
+                    let applets = applets_mut(config, section);
+                    if index < applets.len() {
+                        applets.remove(index);
+                    }
+                });
+            }
+            Message::Move(section, index, direction) => {
+                return self.write(|config| {
+                    let applets = applets_mut(config, section);
+                    let target = match direction {
+                        Direction::Up => index.checked_sub(1),
+                        Direction::Down => Some(index + 1).filter(|i| *i < applets.len()),
+                    };
+                    if let Some(target) = target {
+                        applets.swap(index, target);
+                    }
+                });
+            }
+            Message::MoveToSection(from, index, to) => {
+                return self.write(|config| {
+                    if index < applets(config, from).len() {
+                        let applet = applets_mut(config, from).remove(index);
+                        applets_mut(config, to).push(applet);
+                    }
+                });
+            }
+            Message::Refresh => self.reload(),
+        }
+        Task::none()
+    }
+}
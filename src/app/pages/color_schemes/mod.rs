@@ -23,6 +23,12 @@ use nucleo::{
 };
 use serde::{Deserialize, Serialize};
 
+use crate::Error;
+use crate::app::{
+    page::Page,
+    report::Report,
+    search::{SearchItem, Target},
+};
 use crate::localize::LANGUAGE_SORTER;
 mod view;
 
@@ -59,12 +65,96 @@ pub struct ColorSchemes {
     sort_by: SortBy,
     needle: Option<Atom>,
     matcher: RefCell<Matcher>,
+    /// The scheme the global search jumped to.
+    highlight: Option<String>,
+    /// The theme of the current mode as last read from disk.
+    synced_theme: Option<ThemeBuilder>,
 }
 
 impl ColorSchemes {
+    /// The installed schemes for the global search.
+    pub fn search_items(&self) -> Vec<SearchItem> {
+        self.installed
+            .keys()
+            .map(|name| {
+                SearchItem::new(
+                    Page::ColorSchemes,
+                    name.clone(),
+                    Target::ColorScheme(name.clone()),
+                )
+            })
+            .collect()
+    }
+
+    /// Outlines the installed scheme the global search jumped to.
+    ///
+    /// The query typed on the page is kept unless it hides that scheme.
+    pub fn show(&mut self, name: String) {
+        if let Some(entity) = self
+            .model
+            .iter()
+            .find(|entity| self.model.data::<Tab>(*entity) == Some(&Tab::Installed))
+        {
+            self.model.activate(entity);
+        }
+        if !self.values().any(|(_, scheme)| scheme.name == name) {
+            self.set_query(String::new());
+        }
+        self.highlight = Some(name);
+    }
+
+    pub fn set_highlight(&mut self, name: Option<String>) {
+        self.highlight = name;
+    }
+
+    /// Records the scheme as the applied one, reporting a failed write.
+    fn remember(&mut self, color_scheme: ColorScheme) -> Task<Message> {
+        match self
+            .config
+            .set_current_config(&self.config_writer, Some(color_scheme))
+        {
+            Ok(_) => Task::none(),
+            Err(e) => failed(fl!("failed-save-current-color-scheme"), e, None),
+        }
+    }
+
     pub fn set_theme_mode(&mut self, mode: ThemeMode) {
+        if mode.is_dark != self.theme_mode.is_dark {
+            self.synced_theme = None;
+        }
         self.theme_mode = mode;
     }
+
+    /// Takes in the theme of a mode after it changed on disk.
+    ///
+    /// The scheme marked as current is only kept while it is the one applied,
+    /// otherwise the installed scheme matching the new theme is marked, if any.
+    /// Returns whether the applied theme changed, the first notification only
+    /// telling which one is applied.
+    pub fn sync_theme(&mut self, is_dark: bool, theme_builder: ThemeBuilder) -> bool {
+        if is_dark != self.theme_mode.is_dark {
+            return false;
+        }
+        if !self
+            .config
+            .current_config
+            .as_ref()
+            .is_some_and(|current| current.theme_builder == theme_builder)
+        {
+            self.config.current_config = self
+                .installed
+                .values()
+                .find(|color_scheme| color_scheme.theme_builder == theme_builder)
+                .cloned();
+        }
+        let changed = self
+            .synced_theme
+            .as_ref()
+            .is_some_and(|synced| *synced != theme_builder);
+        self.synced_theme = Some(theme_builder);
+        changed
+    }
+
     pub fn new() -> (Self, Task<Message>) {
         let config = match ColorSchemesPageConfig::get_entry(&ColorSchemesPageConfig::config()) {
             Ok(config) => config,
@@ -118,6 +208,8 @@ impl ColorSchemes {
             sort_by: SortBy::default(),
             needle: None,
             matcher: Matcher::new(nucleo::Config::DEFAULT).into(),
+            highlight: None,
+            synced_theme: None,
         };
 
         let mut tasks = vec![];
@@ -127,7 +219,7 @@ impl ColorSchemes {
                 async { download_themes().await },
                 |res| match res {
                     Ok(themes) => Message::SetAvailableColorSchemes(themes),
-                    Err(e) => Message::Error(MessageErrorKind::Fetching, format!("{e}")),
+                    Err(e) => fetch_failed(e),
                 },
             ));
         }
@@ -158,12 +250,14 @@ pub enum ColorSchemeKey {
 pub enum Message {
     StartImport,
     ImportFilePickerResult(Arc<SelectedFiles>),
-    Error(MessageErrorKind, String),
+    Error(MessageErrorKind, Report),
     // currently, the None variant is intercepted in the outer update fn
     SaveCurrentColorScheme(Option<String>),
     InstallColorScheme(ColorSchemeKey),
     SetColorScheme(ColorSchemeKey),
     SetColorSchemeWithRollBack(ColorSchemeKey),
+    /// A scheme applied outside the page, such as over D-Bus.
+    Applied(ColorScheme),
     RevertOldTheme,
     DeleteColorScheme(ColorSchemeKey),
     SetAvailableColorSchemes(Vec<ColorScheme>),
@@ -280,71 +374,69 @@ impl ColorSchemes {
                         .await?
                         .response()
                 },
-                |res| {
-                    if let Ok(f) = res {
-                        Message::ImportFilePickerResult(Arc::new(f))
-                    } else {
-                        Message::Error(
-                            MessageErrorKind::Other,
-                            "failed to select a file for importing a custom theme.".into(),
-                        )
-                    }
+                |res| match res {
+                    Ok(f) => Message::ImportFilePickerResult(Arc::new(f)),
+                    Err(e) => Message::Error(
+                        MessageErrorKind::Other,
+                        Report::new(fl!("failed-import-color-scheme"), e),
+                    ),
                 },
             )),
-            Message::Error(kind, m) => {
+            // The app shows the report, the page only stops loading.
+            Message::Error(kind, _) => {
                 if kind == MessageErrorKind::Fetching {
                     self.status = Status::Idle;
                 }
-
-                // TODO Error toast?
-                error!("{m}");
             }
             Message::ImportFilePickerResult(f) => match import_file(f) {
                 Ok(theme) => {
                     self.installed.insert(theme.name.clone(), theme.clone());
                     if let Err(e) = apply_theme(&theme.theme) {
-                        error!("can't apply theme: {e}");
+                        tasks.push(failed(fl!("failed-apply-color-scheme"), e, None));
                     } else {
-                        let _ = self
-                            .config
-                            .set_current_config(&self.config_writer, Some(theme.clone()));
+                        tasks.push(self.remember(theme.clone()));
                         self.saved_color_theme = Some(theme);
                     }
                 }
-                Err(e) => {
-                    error!("can't import file: {e}");
-                }
+                Err(e) => tasks.push(failed(fl!("failed-import-color-scheme"), e, None)),
             },
             Message::SetColorScheme(color_scheme) => {
+                // Schemes can be applied over D-Bus right after being copied
+                // into the themes directory, before the page has seen them.
+                if let ColorSchemeKey::Installed(name) = &color_scheme
+                    && !self.installed.contains_key(name)
+                    && let Ok(installed) = installed_system_themes()
+                {
+                    self.installed = installed.into_iter().map(|e| (e.name.clone(), e)).collect();
+                }
+                let retry = Message::SetColorScheme(color_scheme.clone());
                 let color_scheme = self.get(color_scheme).clone();
                 if let Err(e) = apply_theme(&color_scheme.theme) {
-                    error!("can't apply theme: {e}");
+                    tasks.push(failed(fl!("failed-apply-color-scheme"), e, Some(retry)));
                 } else {
-                    let _ = self
-                        .config
-                        .set_current_config(&self.config_writer, Some(color_scheme.clone()));
+                    tasks.push(self.remember(color_scheme.clone()));
                     self.saved_color_theme = Some(color_scheme);
                 }
             }
+            Message::Applied(color_scheme) => {
+                tasks.push(self.remember(color_scheme.clone()));
+                self.saved_color_theme = Some(color_scheme);
+            }
             Message::SetColorSchemeWithRollBack(color_scheme) => {
-                let color_scheme = self.get(color_scheme);
+                let color_scheme = self.get(color_scheme).clone();
                 if let Err(e) = apply_theme(&color_scheme.theme) {
-                    error!("can't apply theme: {e}");
+                    tasks.push(failed(fl!("failed-apply-color-scheme"), e, None));
                 } else {
-                    let _ = self
-                        .config
-                        .set_current_config(&self.config_writer, Some(color_scheme.clone()));
+                    tasks.push(self.remember(color_scheme));
                 }
             }
             Message::RevertOldTheme => {
-                if let Some(old_theme) = &self.saved_color_theme {
+                if let Some(old_theme) = self.saved_color_theme.clone() {
                     if let Err(e) = apply_theme(&old_theme.theme) {
-                        error!("can't apply theme: {e}");
+                        tasks.push(failed(fl!("failed-apply-color-scheme"), e, None));
                     }
 
-                    let _ = self
-                        .config
-                        .set_current_config(&self.config_writer, Some(old_theme.clone()));
+                    tasks.push(self.remember(old_theme));
                 }
             }
             Message::DeleteColorScheme(color_scheme) => {
@@ -361,9 +453,7 @@ impl ColorSchemes {
                     Ok(theme) => {
                         self.installed.insert(theme.name.clone(), theme);
                     }
-                    Err(e) => {
-                        error!("can't install theme: {e}");
-                    }
+                    Err(e) => tasks.push(failed(fl!("failed-install-color-scheme"), e, None)),
                 }
             }
             Message::FetchAvailableColorSchemes => {
@@ -372,7 +462,7 @@ impl ColorSchemes {
                     async { download_themes().await },
                     |res| match res {
                         Ok(themes) => Message::SetAvailableColorSchemes(themes),
-                        Err(e) => Message::Error(MessageErrorKind::Fetching, format!("{e}")),
+                        Err(e) => fetch_failed(e),
                     },
                 ));
             }
@@ -387,14 +477,20 @@ impl ColorSchemes {
             }
             Message::OpenLink(link) => {
                 if let Err(e) = open::that_detached(link) {
-                    error!("There was an error opening the link: {e}")
+                    tasks.push(Task::done(Message::Error(
+                        MessageErrorKind::Other,
+                        Report::new(fl!("failed-open-link"), e),
+                    )));
                 }
             }
             Message::OpenFolder(path) => {
                 if let Some(path) = path.parent()
                     && let Err(e) = open::that_detached(path)
                 {
-                    error!("There was an error opening that color scheme: {e}")
+                    tasks.push(Task::done(Message::Error(
+                        MessageErrorKind::Other,
+                        Report::new(fl!("failed-open-link"), e),
+                    )));
                 }
             }
             Message::SaveCurrentColorScheme(name) => {
@@ -409,18 +505,12 @@ impl ColorSchemes {
                             Ok(theme) => {
                                 self.installed.insert(theme.name.clone(), theme.clone());
 
-                                let _ = self
-                                    .config
-                                    .set_current_config(&self.config_writer, Some(theme));
-                            }
-                            Err(e) => {
-                                error!("can't install theme: {e}");
+                                tasks.push(self.remember(theme));
                             }
+                            Err(e) => tasks.push(failed(fl!("failed-save-color-scheme"), e, None)),
                         }
                     }
-                    Err(e) => {
-                        error!("can't get current theme: {e}");
-                    }
+                    Err(e) => tasks.push(failed(fl!("failed-read-current-theme"), e, None)),
                 }
             }
             Message::ToggleDarkMode(dark) => {
@@ -547,6 +637,36 @@ fn cache_themes_file_path() -> PathBuf {
         .join("tweaks/available_themes.json")
 }
 
+/// Reports a failure of the color scheme helpers, which return `anyhow` errors,
+/// or of the page config, offering to send `retry` again when given.
+fn failed(context: String, error: impl std::fmt::Display, retry: Option<Message>) -> Task<Message> {
+    let report = Report::new(context, Error::ColorScheme(error.to_string()));
+    Task::done(Message::Error(
+        MessageErrorKind::Other,
+        match retry {
+            Some(retry) => {
+                report.retry(crate::app::message::Message::ColorSchemes(Box::new(retry)))
+            }
+            None => report,
+        },
+    ))
+}
+
+/// Reports a failed download of the available color schemes, which can be
+/// retried.
+fn fetch_failed(error: anyhow::Error) -> Message {
+    Message::Error(
+        MessageErrorKind::Fetching,
+        Report::new(
+            fl!("failed-fetch-color-schemes"),
+            Error::ColorScheme(error.to_string()),
+        )
+        .retry(crate::app::message::Message::ColorSchemes(Box::new(
+            Message::FetchAvailableColorSchemes,
+        ))),
+    )
+}
+
 pub fn is_cache_exist() -> bool {
     cache_themes_file_path().exists()
 }
@@ -591,7 +711,7 @@ pub fn apply_theme(theme: &Theme) -> anyhow::Result<()> {
     Ok(())
 }
 
-fn get_current_theme() -> anyhow::Result<ThemeBuilder> {
+pub fn get_current_theme() -> anyhow::Result<ThemeBuilder> {
     let theme_mode_config = ThemeMode::config()?;
 
     let theme_mode = ThemeMode::get_entry(&theme_mode_config).unwrap();
@@ -615,7 +735,7 @@ fn get_current_theme() -> anyhow::Result<ThemeBuilder> {
     Ok(theme_builder)
 }
 
-fn installed_system_themes() -> anyhow::Result<Vec<ColorScheme>> {
+pub fn installed_system_themes() -> anyhow::Result<Vec<ColorScheme>> {
     let mut cosmic_themes = vec![];
 
     let xdg_data_home = std::env::var("XDG_DATA_HOME")
@@ -2,7 +2,7 @@ use crate::{
     app::{
         core::{
             grid::GridMetrics,
-            style::{destructive_button, link_button, standard_button},
+            style::{self, destructive_button, link_button, standard_button},
         },
         pages::{
             ColorSchemes,
@@ -93,19 +93,24 @@ impl ColorSchemes {
                         grid = grid.insert_row();
                         col = 0;
                     }
-                    grid = grid.push(
-                        self.installed(
-                            key,
-                            color_scheme,
-                            self.config
-                                .current_config
-                                .as_ref()
-                                .map(|c| c.name == color_scheme.name)
-                                .unwrap_or(false),
-                            &spacing,
-                            item_width,
-                        ),
+                    let item = self.installed(
+                        key,
+                        color_scheme,
+                        self.config
+                            .current_config
+                            .as_ref()
+                            .map(|c| c.name == color_scheme.name)
+                            .unwrap_or(false),
+                        &spacing,
+                        item_width,
                     );
+                    grid = grid.push(if self.highlight.as_ref() == Some(&color_scheme.name) {
+                        container(item)
+                            .class(cosmic::theme::Container::custom(style::highlight))
+                            .into()
+                    } else {
+                        item
+                    });
                     col += 1;
                 }
 
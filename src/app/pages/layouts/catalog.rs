@@ -0,0 +1,175 @@
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use super::config::Layout;
+use crate::app::core::versioned;
+
+/// A layout published in the online catalog.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatalogLayout {
+    pub layout: Layout,
+    pub author: Option<String>,
+    pub link: Option<String>,
+    pub downloads: Option<u64>,
+    pub updated: Option<i64>,
+}
+
+pub async fn download_layouts(url: String) -> anyhow::Result<Vec<CatalogLayout>> {
+    #[derive(Deserialize)]
+    struct CatalogLayoutHelper {
+        pub ron: String,
+        pub author: Option<String>,
+        pub link: Option<String>,
+        pub downloads: u64,
+        pub updated: String,
+    }
+
+    impl TryFrom<CatalogLayoutHelper> for CatalogLayout {
+        type Error = anyhow::Error;
+
+        fn try_from(value: CatalogLayoutHelper) -> Result<Self, Self::Error> {
+            let layout = versioned::from_str::<Layout>(&value.ron)?;
+            layout.validate()?;
+
+            Ok(Self {
+                layout,
+                author: value.author.filter(|a| !a.is_empty()),
+                link: value.link.filter(|l| !l.is_empty()),
+                downloads: Some(value.downloads),
+                updated: Some(
+                    chrono::DateTime::parse_from_rfc3339(&value.updated)?.timestamp_millis(),
+                ),
+            })
+        }
+    }
+
+    let response = reqwest::get(url).await?.error_for_status()?;
+    let layouts: Vec<CatalogLayoutHelper> = response.json().await?;
+
+    // Entries written for a newer Tweaks are skipped instead of failing the catalog.
+    let layouts = layouts
+        .into_iter()
+        .filter_map(|helper| match CatalogLayout::try_from(helper) {
+            Ok(layout) => Some(layout),
+            Err(e) => {
+                log::warn!("Skipping catalog layout: {e}");
+                None
+            }
+        })
+        .collect();
+
+    Ok(layouts)
+}
+
+/// The layouts last downloaded, with the catalog they came from.
+#[derive(Serialize, Deserialize)]
+struct Cache {
+    url: String,
+    layouts: Vec<CatalogLayout>,
+}
+
+fn cache_layouts_file_path() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap()
+        .join("tweaks/available_layouts.json")
+}
+
+pub fn cache_layouts(url: &str, layouts: &[CatalogLayout]) -> anyhow::Result<()> {
+    let filepath = cache_layouts_file_path();
+
+    std::fs::create_dir_all(filepath.parent().unwrap())?;
+
+    let file = File::create(&filepath)?;
+    let writer = BufWriter::new(file);
+
+    serde_json::to_writer(
+        writer,
+        &Cache {
+            url: url.to_string(),
+            layouts: layouts.to_vec(),
+        },
+    )?;
+
+    Ok(())
+}
+
+/// The cached layouts of a catalog, if the cache was filled from it.
+pub fn get_layouts_from_cache(url: &str) -> anyhow::Result<Option<Vec<CatalogLayout>>> {
+    let filepath = cache_layouts_file_path();
+    if !filepath.exists() {
+        return Ok(None);
+    }
+
+    let file = File::open(&filepath)?;
+    let reader = BufReader::new(file);
+
+    let cache: Cache = serde_json::from_reader(reader)?;
+
+    Ok((cache.url == url).then_some(cache.layouts))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    use super::*;
+
+    /// Serves one HTTP response with a JSON body on a local port.
+    fn serve(body: String) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0; 4096];
+            let _ = stream.read(&mut request).unwrap();
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+        });
+        format!("http://{address}/layouts")
+    }
+
+    fn entry(ron: &str) -> serde_json::Value {
+        serde_json::json!({
+            "ron": ron,
+            "author": "Tweaks",
+            "link": "",
+            "downloads": 42,
+            "updated": "2024-05-01T12:00:00Z",
+        })
+    }
+
+    #[tokio::test]
+    async fn downloads_layouts_from_a_catalog() {
+        let layout = include_str!("../../../../res/layouts/mac.ron");
+        let newer = layout.replacen("version: 2", "version: 99", 1);
+        let body = serde_json::json!([entry(layout), entry(&newer), entry("(broken")]);
+
+        let layouts = download_layouts(serve(body.to_string())).await.unwrap();
+
+        // Only the readable entry is kept.
+        assert_eq!(layouts.len(), 1);
+        let available = &layouts[0];
+        assert_eq!(
+            available.layout.id,
+            versioned::from_str::<Layout>(layout).unwrap().id
+        );
+        assert_eq!(available.author.as_deref(), Some("Tweaks"));
+        assert_eq!(available.link, None);
+        assert_eq!(available.downloads, Some(42));
+        assert_eq!(available.updated, Some(1_714_564_800_000));
+    }
+
+    #[tokio::test]
+    async fn fails_on_a_response_that_is_not_a_catalog() {
+        let url = serve(serde_json::json!({ "error": "not found" }).to_string());
+        assert!(download_layouts(url).await.is_err());
+    }
+}
@@ -1,28 +1,56 @@
-use crate::{Error, app::App};
+use std::path::{Path, PathBuf};
 
-use super::{Message, preview::LayoutPreview};
+use crate::{
+    Error,
+    app::{
+        App,
+        core::versioned::{self, Incompatible, Versioned},
+    },
+    fl,
+};
+
+use super::{Message, outputs, preview::LayoutPreview};
 use cosmic::{Application, Element, widget};
-use cosmic_ext_config_templates::{Schema, panel::PanelSchema};
+use cosmic_ext_config_templates::{Schema, load_template};
+use cosmic_panel_config::CosmicPanelConfig;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+/// The layouts shipped with Tweaks, keyed by the slug older versions used as file name.
+const BUNDLED: [(&str, &str); 4] = [
+    ("cosmic", include_str!("../../../../res/layouts/cosmic.ron")),
+    ("mac", include_str!("../../../../res/layouts/mac.ron")),
+    (
+        "windows",
+        include_str!("../../../../res/layouts/windows.ron"),
+    ),
+    ("ubuntu", include_str!("../../../../res/layouts/ubuntu.ron")),
+];
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Layout {
+    #[serde(default)]
+    pub version: u32,
     pub id: Uuid,
     pub name: String,
     pub custom: bool,
     pub schema: Schema,
-    pub preview: LayoutPreview,
 }
 
 impl Layout {
-    pub fn new(name: String, preview: LayoutPreview) -> Self {
+    /// Writes the panels of the layout, placed on the connected outputs.
+    pub fn apply(&self) -> Result<(), Error> {
+        load_template(outputs::resolve_schema(self.schema.clone()))
+            .map_err(|e| Error::Template(e.to_string()))
+    }
+
+    pub fn new(name: String, schema: Schema) -> Self {
         Self {
+            version: Self::VERSION,
             id: Uuid::new_v4(),
             name,
             custom: true,
-            schema: Schema::Panel(PanelSchema::generate().unwrap()),
-            preview,
+            schema,
         }
     }
 
@@ -33,7 +61,8 @@ impl Layout {
         preview_height: u16,
         selected_layout: &Option<Layout>,
     ) -> Element<'a, Message> {
-        let mut button = widget::button::custom(self.preview.view(spacing, preview_height))
+        let preview = LayoutPreview::from(&self.schema);
+        let mut button = widget::button::custom(preview.view(spacing, preview_height))
             .on_press(Message::Select(self.clone()))
             .class(cosmic::style::Button::Image)
             .width(item_width as f32);
@@ -43,22 +72,181 @@ impl Layout {
         button.into()
     }
 
-    pub fn list() -> Result<Vec<Layout>, Error> {
-        let mut layouts = Vec::new();
-        let layouts_dir = dirs::data_local_dir()
+    pub fn dir() -> Result<PathBuf, Error> {
+        dirs::data_local_dir()
             .map(|path| path.join(App::APP_ID).join("layouts"))
-            .ok_or(Error::LayoutPathNotFound)?;
-
-        if let Ok(entries) = std::fs::read_dir(layouts_dir) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.extension().and_then(|s| s.to_str()) == Some("ron") {
-                    let contents = std::fs::read_to_string(&path)?;
-                    let layout = ron::from_str::<Layout>(&contents)?;
-                    layouts.push(layout);
-                }
+            .ok_or(Error::LayoutPathNotFound)
+    }
+
+    /// The built-in layouts, read from the embedded resources.
+    pub fn bundled() -> Vec<Layout> {
+        Self::bundled_by_slug()
+            .into_iter()
+            .map(|(_, layout)| layout)
+            .collect()
+    }
+
+    /// The built-in layouts with their slug, which unlike the name never
+    /// changes.
+    pub fn bundled_by_slug() -> Vec<(&'static str, Layout)> {
+        BUNDLED
+            .iter()
+            .filter_map(|(slug, contents)| {
+                versioned::from_str::<Layout>(contents)
+                    .map(|layout| {
+                        (
+                            *slug,
+                            Layout {
+                                custom: false,
+                                ..layout
+                            },
+                        )
+                    })
+                    .map_err(|e| log::error!("Failed to read the bundled {slug} layout: {e}"))
+                    .ok()
+            })
+            .collect()
+    }
+
+    /// Lists the built-in layouts followed by the custom ones in the data dir.
+    pub fn list() -> Result<(Vec<Layout>, Vec<Incompatible>), Error> {
+        let mut layouts = Self::bundled();
+        let (custom, incompatible) = versioned::read_dir::<Layout>(&Self::dir()?);
+        let custom: Vec<Layout> = custom
+            .into_iter()
+            .filter(|layout| layout.custom && !layouts.iter().any(|l| l.id == layout.id))
+            .collect();
+        layouts.extend(custom);
+        Ok((layouts, incompatible))
+    }
+
+    /// Removes the copies of built-in layouts older versions wrote on every launch.
+    pub fn remove_legacy_copies() -> Result<(), Error> {
+        let dir = Self::dir()?;
+        for (slug, _) in BUNDLED {
+            let path = dir.join(slug).with_extension("ron");
+            if !path.exists() {
+                continue;
+            }
+            match versioned::read::<Layout>(&path) {
+                Ok(layout) if !layout.custom => std::fs::remove_file(&path)?,
+                Ok(_) => {}
+                Err(e) => log::warn!("Keeping {}: {}", path.display(), e.reason),
             }
         }
-        Ok(layouts)
+        Ok(())
+    }
+
+    pub fn path(&self) -> Result<PathBuf, Error> {
+        Ok(Self::dir()?.join(self.id.to_string()).with_extension("ron"))
+    }
+
+    pub fn save(&self) -> Result<(), Error> {
+        self.export(&self.path()?)
+    }
+
+    /// Checks that the schema describes panels that can be applied.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.name.trim().is_empty() {
+            return Err(Error::InvalidLayout("the layout has no name".into()));
+        }
+        let configs = self.configs();
+        if configs.is_empty() {
+            return Err(Error::InvalidLayout("the layout has no panels".into()));
+        }
+        for (index, config) in configs.iter().enumerate() {
+            if config.name.trim().is_empty() {
+                return Err(Error::InvalidLayout(format!("panel {index} has no name")));
+            }
+            if configs[..index]
+                .iter()
+                .any(|other| other.name == config.name)
+            {
+                return Err(Error::InvalidLayout(format!(
+                    "panel name {} is used twice",
+                    config.name
+                )));
+            }
+            if !(0.0..=1.0).contains(&config.opacity) {
+                return Err(Error::InvalidLayout(format!(
+                    "panel {} has an opacity outside of 0 to 1",
+                    config.name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads a layout from a file chosen by the user and stores it as a custom layout.
+    pub fn import(path: &Path, existing: &[Layout]) -> Result<Layout, Error> {
+        let contents = std::fs::read_to_string(path)?;
+        versioned::from_str::<Layout>(&contents)?.install(existing)
+    }
+
+    /// Stores a layout obtained from outside the data dir as a custom layout.
+    ///
+    /// A layout whose id is already taken gets a new one, so installing a
+    /// built-in or a previously installed layout never replaces it.
+    pub fn install(mut self, existing: &[Layout]) -> Result<Layout, Error> {
+        self.validate()?;
+
+        if existing.iter().any(|l| l.id == self.id) || self.path()?.exists() {
+            self.id = Uuid::new_v4();
+        }
+        self.custom = true;
+        self.save()?;
+
+        Ok(self)
+    }
+
+    /// Creates an editable custom copy of this layout.
+    pub fn duplicate(&self) -> Layout {
+        Layout::new(
+            fl!("layout-copy", name = self.name.as_str()),
+            self.schema.clone(),
+        )
+    }
+
+    pub fn delete(&self) -> Result<(), Error> {
+        std::fs::remove_file(self.path()?)?;
+        Ok(())
+    }
+
+    pub fn export(&self, path: &Path) -> Result<(), Error> {
+        let data = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        std::fs::write(path, data)?;
+        Ok(())
+    }
+
+    pub fn configs(&self) -> &[CosmicPanelConfig] {
+        match &self.schema {
+            Schema::Panel(panel_schema) => &panel_schema.panel_config.config_list,
+        }
+    }
+
+    pub fn configs_mut(&mut self) -> &mut Vec<CosmicPanelConfig> {
+        match &mut self.schema {
+            Schema::Panel(panel_schema) => &mut panel_schema.panel_config.config_list,
+        }
+    }
+}
+
+impl Versioned for Layout {
+    const VERSION: u32 = 2;
+
+    fn version(&self) -> u32 {
+        self.version
+    }
+
+    fn migrate(contents: &str, version: u32) -> Result<Self, Error> {
+        match version {
+            // Version 0 is the unversioned format, which only lacks the field,
+            // and version 1 also stored the preview, now derived from the schema.
+            0 | 1 => Ok(Self {
+                version: Self::VERSION,
+                ..ron::from_str(contents)?
+            }),
+            version => Err(Error::UnsupportedVersion(version)),
+        }
     }
 }
@@ -0,0 +1,565 @@
+use std::borrow::Cow;
+
+use cosmic::{
+    Element,
+    iced::{
+        Alignment, Length,
+        clipboard::mime::{AllowedMimeTypes, AsMimeTypes},
+    },
+    widget::{self, dnd_destination::DndDestination, segmented_button},
+};
+use cosmic_panel_config::{CosmicPanelConfig, CosmicPanelOuput, PanelAnchor, PanelSize};
+
+use super::{config::Layout, outputs, preview::LayoutPreview};
+use crate::app::pages::panel::size;
+use crate::{fl, icon};
+
+/// Edits the panels and applets of a layout without touching the desktop.
+pub struct LayoutEditor {
+    pub layout: Layout,
+    selected: usize,
+    anchor_model: segmented_button::SingleSelectModel,
+    /// Outputs connected when the editor was opened.
+    connected: Vec<String>,
+    /// Placements offered for the selected panel, and their labels.
+    output_options: Vec<CosmicPanelOuput>,
+    output_labels: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Section {
+    Start,
+    Center,
+    End,
+}
+
+/// An applet dragged within the selected panel, by where it was taken from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DraggedApplet {
+    pub section: Section,
+    pub index: usize,
+}
+
+const APPLET_MIME: &str = "application/x-cosmic-tweaks-applet";
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    SetName(String),
+    SelectPanel(usize),
+    AddPanel,
+    RemovePanel(usize),
+    DuplicatePanel(usize),
+    MovePanel(usize, Direction),
+    SetOutput(usize),
+    SetAnchor(segmented_button::Entity),
+    SetSize(u32),
+    SetMargin(u16),
+    SetOpacity(u32),
+    SetExpand(bool),
+    /// Drops an applet before the one at the index of a section, or at its
+    /// end past the last applet.
+    DropApplet(Option<DraggedApplet>, Section, usize),
+    RemoveApplet(Section, usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+}
+
+impl Section {
+    pub fn title(&self) -> String {
+        match self {
+            Section::Start => fl!("applets-start"),
+            Section::Center => fl!("applets-center"),
+            Section::End => fl!("applets-end"),
+        }
+    }
+
+    pub fn previous(&self) -> Option<Section> {
+        match self {
+            Section::Start => None,
+            Section::Center => Some(Section::Start),
+            Section::End => Some(Section::Center),
+        }
+    }
+
+    pub fn next(&self) -> Option<Section> {
+        match self {
+            Section::Start => Some(Section::Center),
+            Section::Center => Some(Section::End),
+            Section::End => None,
+        }
+    }
+}
+
+impl AsMimeTypes for DraggedApplet {
+    fn available(&self) -> Cow<'static, [String]> {
+        Cow::Owned(vec![APPLET_MIME.to_string()])
+    }
+
+    fn as_bytes(&self, mime_type: &str) -> Option<Cow<'static, [u8]>> {
+        (mime_type == APPLET_MIME)
+            .then(|| Cow::Owned(format!("{:?} {}", self.section, self.index).into_bytes()))
+    }
+}
+
+impl AllowedMimeTypes for DraggedApplet {
+    fn allowed() -> Cow<'static, [String]> {
+        Cow::Owned(vec![APPLET_MIME.to_string()])
+    }
+}
+
+impl TryFrom<(Vec<u8>, String)> for DraggedApplet {
+    type Error = ();
+
+    fn try_from((data, _): (Vec<u8>, String)) -> Result<Self, Self::Error> {
+        let data = String::from_utf8(data).map_err(|_| ())?;
+        let (section, index) = data.split_once(' ').ok_or(())?;
+        let section = match section {
+            "Start" => Section::Start,
+            "Center" => Section::Center,
+            "End" => Section::End,
+            _ => return Err(()),
+        };
+        Ok(Self {
+            section,
+            index: index.parse().map_err(|_| ())?,
+        })
+    }
+}
+
+/// The name shown for a panel.
+///
+/// Panel names are cosmic-config ids, so they are never translated, but the
+/// default "Panel" and "Dock" and the "Panel 2" style names given to added
+/// panels are shown translated.
+pub fn display_name(name: &str) -> String {
+    match name {
+        "Panel" => fl!("panel"),
+        "Dock" => fl!("dock"),
+        _ => match name
+            .strip_prefix("Panel ")
+            .filter(|number| number.parse::<usize>().is_ok())
+        {
+            Some(number) => format!("{} {number}", fl!("panel")),
+            None => name.to_string(),
+        },
+    }
+}
+
+/// Returns the applets of a panel section, creating it if it is unset.
+pub fn applets_mut(config: &mut CosmicPanelConfig, section: Section) -> &mut Vec<String> {
+    match section {
+        Section::Start => &mut config.plugins_wings.get_or_insert_with(Default::default).0,
+        Section::Center => config.plugins_center.get_or_insert_with(Default::default),
+        Section::End => &mut config.plugins_wings.get_or_insert_with(Default::default).1,
+    }
+}
+
+pub fn applets(config: &CosmicPanelConfig, section: Section) -> &[String] {
+    match section {
+        Section::Start => config
+            .plugins_wings
+            .as_ref()
+            .map(|wings| wings.0.as_slice())
+            .unwrap_or_default(),
+        Section::Center => config.plugins_center.as_deref().unwrap_or_default(),
+        Section::End => config
+            .plugins_wings
+            .as_ref()
+            .map(|wings| wings.1.as_slice())
+            .unwrap_or_default(),
+    }
+}
+
+/// A readable name for an applet id such as `com.system76.CosmicAppletTime`.
+pub fn applet_name(id: &str) -> &str {
+    id.rsplit('.').next().unwrap_or(id)
+}
+
+impl LayoutEditor {
+    pub fn new(layout: Layout) -> Self {
+        let mut editor = Self {
+            layout,
+            selected: 0,
+            anchor_model: segmented_button::Model::builder()
+                .insert(|b| b.text(fl!("left")).data(PanelAnchor::Left))
+                .insert(|b| b.text(fl!("top")).data(PanelAnchor::Top))
+                .insert(|b| b.text(fl!("right")).data(PanelAnchor::Right))
+                .insert(|b| b.text(fl!("bottom")).data(PanelAnchor::Bottom))
+                .build(),
+            connected: outputs::connected(),
+            output_options: Vec::new(),
+            output_labels: Vec::new(),
+        };
+        editor.select(0);
+        editor
+    }
+
+    /// Offers every connected output and every output named by the layout,
+    /// so panels placed on a missing output can still be moved.
+    fn refresh_outputs(&mut self) {
+        let mut names = self.connected.clone();
+        for name in outputs::named(self.layout.configs()) {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+
+        self.output_options = [CosmicPanelOuput::All, CosmicPanelOuput::Active]
+            .into_iter()
+            .chain(names.into_iter().map(CosmicPanelOuput::Name))
+            .collect();
+        self.output_labels = self
+            .output_options
+            .iter()
+            .map(|output| match output {
+                CosmicPanelOuput::All => fl!("all-outputs"),
+                CosmicPanelOuput::Active => fl!("active-output"),
+                CosmicPanelOuput::Name(name) if self.connected.contains(name) => name.clone(),
+                CosmicPanelOuput::Name(name) => {
+                    fl!("disconnected-output", name = name.as_str())
+                }
+            })
+            .collect();
+    }
+
+    fn select(&mut self, index: usize) {
+        self.selected = index.min(self.layout.configs().len().saturating_sub(1));
+        self.refresh_outputs();
+        let Some(anchor) = self.selected_config().map(|config| config.anchor) else {
+            return;
+        };
+        let entity = self
+            .anchor_model
+            .iter()
+            .find(|entity| self.anchor_model.data::<PanelAnchor>(*entity) == Some(&anchor));
+        if let Some(entity) = entity {
+            self.anchor_model.activate(entity);
+        }
+    }
+
+    fn selected_config(&self) -> Option<&CosmicPanelConfig> {
+        self.layout.configs().get(self.selected)
+    }
+
+    fn selected_config_mut(&mut self) -> Option<&mut CosmicPanelConfig> {
+        self.layout.configs_mut().get_mut(self.selected)
+    }
+
+    pub fn update(&mut self, message: Message) {
+        match message {
+            Message::SetName(name) => self.layout.name = name,
+            Message::SelectPanel(index) => self.select(index),
+            Message::AddPanel => {
+                let configs = self.layout.configs_mut();
+                let mut index = configs.len() + 1;
+                let name = loop {
+                    let name = format!("Panel {index}");
+                    if !configs.iter().any(|config| config.name == name) {
+                        break name;
+                    }
+                    index += 1;
+                };
+                configs.push(CosmicPanelConfig {
+                    name,
+                    ..Default::default()
+                });
+                let last = configs.len() - 1;
+                self.select(last);
+            }
+            Message::RemovePanel(index) => {
+                let configs = self.layout.configs_mut();
+                if index < configs.len() {
+                    configs.remove(index);
+                }
+                self.select(self.selected);
+            }
+            Message::DuplicatePanel(index) => {
+                let configs = self.layout.configs_mut();
+                if let Some(config) = configs.get(index).cloned() {
+                    let mut copy = 1;
+                    let name = loop {
+                        let name = format!("{} {copy}", config.name);
+                        if !configs.iter().any(|config| config.name == name) {
+                            break name;
+                        }
+                        copy += 1;
+                    };
+                    configs.insert(index + 1, CosmicPanelConfig { name, ..config });
+                    self.select(index + 1);
+                }
+            }
+            Message::SetOutput(index) => {
+                if let Some(output) = self.output_options.get(index).cloned()
+                    && let Some(config) = self.selected_config_mut()
+                {
+                    config.output = output;
+                }
+            }
+            Message::MovePanel(index, direction) => {
+                let configs = self.layout.configs_mut();
+                let target = match direction {
+                    Direction::Up => index.checked_sub(1),
+                    Direction::Down => Some(index + 1).filter(|i| *i < configs.len()),
+                };
+                if let Some(target) = target {
+                    configs.swap(index, target);
+                    self.select(target);
+                }
+            }
+            Message::SetAnchor(entity) => {
+                self.anchor_model.activate(entity);
+                if let Some(anchor) = self.anchor_model.data::<PanelAnchor>(entity).copied()
+                    && let Some(config) = self.selected_config_mut()
+                {
+                    config.anchor = anchor;
+                }
+            }
+            Message::SetSize(size) => {
+                if let Some(config) = self.selected_config_mut() {
+                    config.size = PanelSize::Custom(size);
+                }
+            }
+            Message::SetMargin(margin) => {
+                if let Some(config) = self.selected_config_mut() {
+                    config.margin = margin;
+                }
+            }
+            Message::SetOpacity(opacity) => {
+                if let Some(config) = self.selected_config_mut() {
+                    config.opacity = opacity as f32 / 100.0;
+                }
+            }
+            Message::SetExpand(expand) => {
+                if let Some(config) = self.selected_config_mut() {
+                    config.expand_to_edges = expand;
+                }
+            }
+            Message::DropApplet(dragged, section, mut index) => {
+                if let Some(dragged) = dragged
+                    && let Some(config) = self.selected_config_mut()
+                    && dragged.index < applets(config, dragged.section).len()
+                {
+                    let applet = applets_mut(config, dragged.section).remove(dragged.index);
+                    // Taking the applet out shifts the later ones of its section.
+                    if dragged.section == section && dragged.index < index {
+                        index -= 1;
+                    }
+                    let applets = applets_mut(config, section);
+                    applets.insert(index.min(applets.len()), applet);
+                }
+            }
+            Message::RemoveApplet(section, index) => {
+                if let Some(config) = self.selected_config_mut() {
+                    let applets = applets_mut(config, section);
+                    if index < applets.len() {
+                        applets.remove(index);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn view<'a>(&'a self) -> Element<'a, Message> {
+        let spacing = cosmic::theme::spacing();
+        let previews = widget::row::with_children(
+            LayoutPreview::per_output(&self.layout.schema)
+                .into_iter()
+                .map(|(output, preview)| {
+                    widget::column()
+                        .push(preview.view(&spacing, 180))
+                        .push(widget::text::caption(
+                            output.unwrap_or_else(|| fl!("other-outputs")),
+                        ))
+                        .spacing(spacing.space_xxs)
+                        .align_x(Alignment::Center)
+                        .width(Length::Fill)
+                        .into()
+                })
+                .collect(),
+        )
+        .spacing(spacing.space_xs);
+
+        let panels = self.layout.configs().iter().enumerate().fold(
+            widget::settings::section().title(fl!("panels")),
+            |section, (index, config)| {
+                let controls = widget::row()
+                    .push(
+                        widget::button::icon(widget::icon::from_name("edit-symbolic"))
+                            .on_press(Message::SelectPanel(index)),
+                    )
+                    .push(
+                        widget::button::icon(widget::icon::from_name("go-up-symbolic"))
+                            .on_press(Message::MovePanel(index, Direction::Up)),
+                    )
+                    .push(
+                        widget::button::icon(widget::icon::from_name("go-down-symbolic"))
+                            .on_press(Message::MovePanel(index, Direction::Down)),
+                    )
+                    .push(
+                        widget::button::icon(widget::icon::from_name("edit-copy-symbolic"))
+                            .on_press(Message::DuplicatePanel(index)),
+                    )
+                    .push(
+                        widget::button::icon(widget::icon::from_name("user-trash-symbolic"))
+                            .class(cosmic::style::Button::Destructive)
+                            .on_press(Message::RemovePanel(index)),
+                    )
+                    .spacing(spacing.space_xxs);
+                let mut item = widget::settings::item::builder(display_name(&config.name));
+                if index == self.selected {
+                    item = item.description(fl!("editing"));
+                } else if let CosmicPanelOuput::Name(output) = &config.output {
+                    item = item.description(output.clone());
+                }
+                section.add(item.control(controls))
+            },
+        );
+        let panels = panels.add(
+            widget::settings::item::builder(fl!("add-panel"))
+                .control(widget::button::standard(fl!("add")).on_press(Message::AddPanel)),
+        );
+
+        let properties = self.selected_config().map(|config| {
+            let panel_size = size::to_u32(config.size.clone());
+            let opacity = (config.opacity * 100.0).round() as u32;
+            widget::settings::section()
+                .title(display_name(&config.name))
+                .add(
+                    widget::settings::item::builder(fl!("position"))
+                        .icon(icon!("resize-mode-symbolic", 18))
+                        .control(
+                            widget::segmented_button::horizontal(&self.anchor_model)
+                                .on_activate(Message::SetAnchor)
+                                .button_alignment(Alignment::Center)
+                                .button_spacing(spacing.space_xxs),
+                        ),
+                )
+                .add(
+                    widget::settings::item::builder(fl!("output"))
+                        .icon(icon!("view-coverflow-symbolic", 18))
+                        .control(widget::dropdown(
+                            &self.output_labels,
+                            self.output_options
+                                .iter()
+                                .position(|output| *output == config.output),
+                            Message::SetOutput,
+                        )),
+                )
+                .add(
+                    widget::settings::item::builder(fl!("extend"))
+                        .icon(icon!("size-horizontally-symbolic", 18))
+                        .toggler(config.expand_to_edges, Message::SetExpand),
+                )
+                .add(
+                    widget::settings::item::builder(fl!("size"))
+                        .icon(icon!("size-vertically-symbolic", 18))
+                        .control(
+                            widget::row()
+                                .push(
+                                    widget::slider(16..=112, panel_size, Message::SetSize)
+                                        .step(4u32),
+                                )
+                                .push(size::name(config.size.clone()))
+                                .spacing(spacing.space_xxs),
+                        ),
+                )
+                .add(
+                    widget::settings::item::builder(fl!("margin"))
+                        .icon(icon!("object-layout-symbolic", 18))
+                        .control(
+                            widget::row()
+                                .push(widget::slider(0..=20, config.margin, Message::SetMargin))
+                                .push(widget::text::text(format!("{} px", config.margin)))
+                                .spacing(spacing.space_xxs),
+                        ),
+                )
+                .add(
+                    widget::settings::item::builder(fl!("opacity"))
+                        .icon(icon!("dark-mode-symbolic", 18))
+                        .control(
+                            widget::row()
+                                .push(widget::slider(0..=100, opacity, Message::SetOpacity))
+                                .push(widget::text::text(format!("{opacity} %")))
+                                .spacing(spacing.space_xxs),
+                        ),
+                )
+        });
+
+        let applets = self.selected_config().map(|config| {
+            widget::row::with_children(
+                [Section::Start, Section::Center, Section::End]
+                    .into_iter()
+                    .map(|section| self.applets_column(config, section))
+                    .collect(),
+            )
+            .spacing(spacing.space_xs)
+        });
+
+        widget::scrollable(
+            widget::column()
+                .push(
+                    widget::text_input(fl!("layout-name"), &self.layout.name)
+                        .on_input(Message::SetName),
+                )
+                .push(previews)
+                .push(panels)
+                .push_maybe(properties)
+                .push_maybe(applets)
+                .spacing(spacing.space_m),
+        )
+        .into()
+    }
+
+    fn applets_column<'a>(
+        &'a self,
+        config: &'a CosmicPanelConfig,
+        section: Section,
+    ) -> Element<'a, Message> {
+        let spacing = cosmic::theme::spacing();
+        let applets = applets(config, section);
+        let items = applets.iter().enumerate().map(|(index, id)| {
+            let row = widget::row()
+                .push(widget::icon::from_name("list-drag-handle-symbolic").size(16))
+                .push(widget::text(applet_name(id)).width(Length::Fill))
+                .push(
+                    widget::button::icon(widget::icon::from_name("list-remove-symbolic"))
+                        .class(cosmic::style::Button::Destructive)
+                        .on_press(Message::RemoveApplet(section, index)),
+                )
+                .spacing(spacing.space_xxs)
+                .align_y(Alignment::Center);
+            let dragged = DraggedApplet { section, index };
+            let source = widget::dnd_source(row).drag_content(move || dragged);
+            drop_target(source, section, index)
+        });
+        // Dropping past the last applet, or into an empty section, appends.
+        let end = widget::container(widget::text::caption(fl!("drop-applet")))
+            .padding(spacing.space_xxs)
+            .width(Length::Fill)
+            .class(cosmic::style::Container::Secondary);
+
+        widget::column()
+            .push(widget::text::heading(section.title()))
+            .extend(items)
+            .push(drop_target(end, section, applets.len()))
+            .spacing(spacing.space_xxs)
+            .width(Length::Fill)
+            .into()
+    }
+}
+
+/// Takes applets dropped on an element, placing them at the index of a section.
+fn drop_target<'a>(
+    element: impl Into<Element<'a, Message>>,
+    section: Section,
+    index: usize,
+) -> Element<'a, Message> {
+    DndDestination::for_data::<DraggedApplet>(element, move |dragged, _| {
+        Message::DropApplet(dragged, section, index)
+    })
+    .into()
+}
@@ -1,48 +1,68 @@
+use std::{path::PathBuf, sync::Arc};
+
+use ashpd::desktop::file_chooser::{FileFilter, SelectedFiles};
+
+use catalog::CatalogLayout;
 use config::Layout;
 use cosmic::{
-    Application, Element, Task,
-    iced::{Length, alignment::Horizontal},
+    Element, Task,
+    iced::{Alignment, Length, alignment::Horizontal},
     widget::{
         self,
         segmented_button::{self, SingleSelect},
     },
 };
-use cosmic_ext_config_templates::load_template;
-use preview::{LayoutPreview, Position};
+use cosmic_ext_config_templates::{Schema, panel::PanelSchema};
+use editor::LayoutEditor;
+use uuid::Uuid;
 
-use crate::app::{App, core::grid::GridMetrics};
-use crate::{Error, fl};
+use crate::app::core::{
+    grid::GridMetrics,
+    versioned::{Incompatible, Versioned},
+};
+use crate::app::{
+    page::Page,
+    report::Report,
+    search::{SearchItem, Target},
+};
+use crate::{Error, fl, icon_handle};
+use preset::Preset;
+use preview::LayoutPreview;
 
+pub mod catalog;
 pub mod config;
 pub mod dialog;
+pub mod editor;
+pub mod outputs;
+pub mod preset;
 pub mod preview;
 
 pub struct Layouts {
     layouts: Vec<Layout>,
+    incompatible: Vec<Incompatible>,
+    available: Vec<CatalogLayout>,
+    presets: Vec<Preset>,
+    model: segmented_button::Model<SingleSelect>,
+    status: Status,
+    query: String,
+    catalog_url: String,
+    hidden: Vec<Uuid>,
     pub selected_layout: Option<Layout>,
-    pub panel_model: segmented_button::Model<SingleSelect>,
-    pub dock_model: segmented_button::Model<SingleSelect>,
+    pub selected_preset: Option<Preset>,
+    pub editor: Option<LayoutEditor>,
 }
 
-impl Default for Layouts {
-    fn default() -> Self {
-        Self {
-            layouts: Vec::new(),
-            selected_layout: None,
-            panel_model: segmented_button::Model::builder()
-                .insert(|b| b.text(fl!("left")).data(Position::Left))
-                .insert(|b| b.text(fl!("top")).data(Position::Top).activate())
-                .insert(|b| b.text(fl!("right")).data(Position::Right))
-                .insert(|b| b.text(fl!("bottom")).data(Position::Bottom))
-                .build(),
-            dock_model: segmented_button::Model::builder()
-                .insert(|b| b.text(fl!("left")).data(Position::Left))
-                .insert(|b| b.text(fl!("top")).data(Position::Top))
-                .insert(|b| b.text(fl!("right")).data(Position::Right))
-                .insert(|b| b.text(fl!("bottom")).data(Position::Bottom).activate())
-                .build(),
-        }
-    }
+#[derive(Debug, Clone, Copy)]
+pub enum Status {
+    Idle,
+    Loading,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tab {
+    Installed,
+    Available,
+    Presets,
 }
 
 #[derive(Debug, Clone)]
@@ -50,39 +70,208 @@ pub enum Message {
     Select(Layout),
     Apply,
     Delete,
-    LoadLayouts(Vec<Layout>),
-    Create(String, LayoutPreview),
+    LoadLayouts(Vec<Layout>, Vec<Incompatible>),
+    // intercepted in the outer update fn to open the dialog
+    SaveCurrentLayout,
+    Create(String, Schema),
+    DeleteIncompatible(PathBuf),
+    NewLayout,
+    Edit,
+    Duplicate,
+    Show(Uuid),
+    SetHiddenLayouts(Vec<Uuid>),
+    Editor(editor::Message),
+    SaveEdit,
+    CancelEdit,
+    StartImport,
+    ImportFilePickerResult(Arc<SelectedFiles>),
+    StartExport,
+    ExportFilePickerResult(Layout, Arc<SelectedFiles>),
+    TabSelected(segmented_button::Entity),
+    Query(String),
+    SetCatalogUrl(String),
+    FetchAvailableLayouts,
+    /// The layouts downloaded from the catalog at an address.
+    SetAvailableLayouts(String, Vec<CatalogLayout>),
+    FetchFailed(String),
+    InstallLayout(usize),
+    OpenLink(String),
+    SelectPreset(Preset),
+    ApplyPreset,
+    DeletePreset,
+    // intercepted in the outer update fn to open the dialog
+    SaveCurrentPreset,
+    CreatePreset(String),
+}
+
+/// The cached layouts of a catalog, if it was downloaded before.
+fn cached_layouts(url: &str) -> Option<Vec<CatalogLayout>> {
+    if url.is_empty() {
+        return None;
+    }
+    catalog::get_layouts_from_cache(url)
+        .map_err(|e| log::error!("can't load layouts from cache: {e}"))
+        .ok()
+        .flatten()
+}
+
+pub fn selected_path(files: &SelectedFiles) -> Result<PathBuf, Error> {
+    files
+        .uris()
+        .first()
+        .filter(|uri| uri.scheme() == "file")
+        .and_then(|uri| uri.to_file_path().ok())
+        .ok_or(Error::NoFileSelected)
 }
 
 impl Layouts {
+    pub fn new(
+        catalog_url: String,
+        hidden: Vec<Uuid>,
+    ) -> (Self, Task<crate::app::message::Message>) {
+        let available = cached_layouts(&catalog_url);
+        let need_fetching = available.is_none() && !catalog_url.is_empty();
+        let available = available.unwrap_or_default();
+
+        let mut layouts = Self {
+            layouts: Vec::new(),
+            incompatible: Vec::new(),
+            available,
+            presets: Vec::new(),
+            model: segmented_button::Model::builder()
+                .insert(|b| b.text(fl!("installed")).data(Tab::Installed).activate())
+                .insert(|b| b.text(fl!("available")).data(Tab::Available))
+                .insert(|b| b.text(fl!("presets")).data(Tab::Presets))
+                .build(),
+            status: Status::Idle,
+            query: String::new(),
+            catalog_url,
+            hidden,
+            selected_layout: None,
+            selected_preset: None,
+            editor: None,
+        };
+        layouts.reload_presets();
+
+        let task = if need_fetching {
+            layouts.update(Message::FetchAvailableLayouts)
+        } else {
+            Task::none()
+        };
+
+        (layouts, task)
+    }
+
+    fn is_hidden(&self, layout: &Layout) -> bool {
+        !layout.custom && self.hidden.contains(&layout.id)
+    }
+
+    fn set_hidden(&mut self, hidden: Vec<Uuid>) -> Task<crate::app::message::Message> {
+        self.hidden = hidden.clone();
+        Task::done(crate::app::message::Message::Settings(
+            crate::app::message::SettingsMessage::HiddenLayouts(hidden),
+        ))
+    }
+
+    fn matches_query(&self, name: &str) -> bool {
+        self.query.is_empty() || name.to_lowercase().contains(&self.query.to_lowercase())
+    }
+
+    /// The installed layouts for the global search.
+    pub fn search_items(&self) -> Vec<SearchItem> {
+        self.layouts
+            .iter()
+            .filter(|layout| !self.is_hidden(layout))
+            .map(|layout| {
+                SearchItem::new(
+                    Page::Layouts,
+                    layout.name.clone(),
+                    Target::Layout(layout.name.clone()),
+                )
+            })
+            .collect()
+    }
+
+    /// Selects the installed layout the global search jumped to.
+    pub fn show(&mut self, name: String) {
+        if let Some(entity) = self
+            .model
+            .iter()
+            .find(|entity| self.model.data::<Tab>(*entity) == Some(&Tab::Installed))
+        {
+            self.model.activate(entity);
+        }
+        self.editor = None;
+        self.selected_layout = self
+            .layouts
+            .iter()
+            .find(|layout| layout.name == name)
+            .cloned();
+        // The query typed on the page is kept unless it hides that layout.
+        if !self.matches_query(&name) {
+            self.query.clear();
+        }
+    }
+
+    pub fn active_tab(&self) -> Tab {
+        self.model
+            .active_data::<Tab>()
+            .copied()
+            .unwrap_or(Tab::Installed)
+    }
+
+    pub fn header_end(&self) -> Vec<Element<'_, Message>> {
+        if self.editor.is_some() {
+            return vec![];
+        }
+
+        vec![
+            widget::search_input(fl!("search"), &self.query)
+                .on_input(Message::Query)
+                .width(200)
+                .into(),
+        ]
+    }
+
     pub fn init() -> Result<(), Error> {
-        let layouts_dir = dirs::data_local_dir()
-            .map(|path| path.join(App::APP_ID).join("layouts"))
-            .ok_or(Error::LayoutPathNotFound)?;
+        let layouts_dir = Layout::dir()?;
 
         if !layouts_dir.exists() {
             std::fs::create_dir_all(&layouts_dir)?;
         }
 
-        let layouts = vec![
-            ("cosmic", include_str!("../../../../res/layouts/cosmic.ron")),
-            ("mac", include_str!("../../../../res/layouts/mac.ron")),
-            (
-                "windows",
-                include_str!("../../../../res/layouts/windows.ron"),
-            ),
-            ("ubuntu", include_str!("../../../../res/layouts/ubuntu.ron")),
-        ];
-
-        for (name, content) in layouts {
-            let file_path = layouts_dir.join(name.to_lowercase()).with_extension("ron");
-            std::fs::write(file_path, content)?;
+        Layout::remove_legacy_copies()
+    }
+
+    fn reload(&mut self) {
+        match Layout::list() {
+            Ok((layouts, incompatible)) => {
+                self.layouts = layouts;
+                self.incompatible = incompatible;
+            }
+            Err(e) => log::error!("Failed to reload layouts: {e}"),
         }
+    }
 
-        Ok(())
+    fn reload_presets(&mut self) {
+        let mut presets = Preset::bundled();
+        match Preset::list() {
+            Ok((custom, incompatible)) => {
+                presets.extend(custom);
+                for preset in incompatible {
+                    log::warn!("Skipping preset {}: {}", preset.name, preset.reason);
+                }
+            }
+            Err(e) => log::error!("Failed to load presets: {e}"),
+        }
+        self.presets = presets;
     }
 
     pub fn view<'a>(&'a self) -> Element<'a, Message> {
+        if let Some(editor) = &self.editor {
+            return editor.view().map(Message::Editor);
+        }
+
         let spacing = cosmic::theme::spacing();
         let grid = widget::responsive(move |size| {
             let GridMetrics {
@@ -93,7 +282,11 @@ impl Layouts {
 
             let mut grid = widget::grid();
             let mut col = 0;
-            for layout in self.layouts.iter() {
+            for layout in self
+                .layouts
+                .iter()
+                .filter(|l| !self.is_hidden(l) && self.matches_query(&l.name))
+            {
                 if col >= cols {
                     grid = grid.insert_row();
                     col = 0;
@@ -116,61 +309,261 @@ impl Layouts {
             .into()
         });
 
+        let incompatible = (!self.incompatible.is_empty()).then(|| {
+            widget::settings::section()
+                .title(fl!("incompatible-layouts"))
+                .extend(self.incompatible.iter().map(|incompatible| {
+                    widget::settings::item::builder(incompatible.name.clone())
+                        .description(incompatible.reason.clone())
+                        .control(
+                            widget::button::destructive(fl!("delete-layout"))
+                                .on_press(Message::DeleteIncompatible(incompatible.path.clone())),
+                        )
+                }))
+        });
+
+        let hidden: Vec<&Layout> = self
+            .layouts
+            .iter()
+            .filter(|layout| self.is_hidden(layout))
+            .collect();
+        let hidden = (!hidden.is_empty()).then(|| {
+            widget::settings::section()
+                .title(fl!("hidden-layouts"))
+                .extend(hidden.into_iter().map(|layout| {
+                    widget::settings::item::builder(layout.name.clone()).control(
+                        widget::button::standard(fl!("show-layout"))
+                            .on_press(Message::Show(layout.id)),
+                    )
+                }))
+        });
+
+        let tabs = widget::segmented_button::horizontal(&self.model)
+            .padding(spacing.space_xxxs)
+            .button_alignment(Alignment::Center)
+            .on_activate(Message::TabSelected);
+
+        let content: Element<_> = match self.active_tab() {
+            Tab::Installed => widget::column()
+                .push_maybe(incompatible)
+                .push(widget::settings::section().title(fl!("layouts")).add(grid))
+                .push_maybe(hidden)
+                .spacing(spacing.space_xs)
+                .into(),
+            Tab::Available => widget::settings::section()
+                .add(self.available_layouts())
+                .into(),
+            Tab::Presets => widget::settings::section()
+                .title(fl!("presets"))
+                .add(self.presets())
+                .into(),
+        };
+
         widget::column()
-            .push(widget::settings::section().title(fl!("layouts")).add(grid))
+            .push(tabs)
+            .push(content)
+            .spacing(spacing.space_xxs)
+            .into()
+    }
+
+    fn presets<'a>(&'a self) -> Element<'a, Message> {
+        widget::responsive(move |size| {
+            let spacing = cosmic::theme::spacing();
+            let GridMetrics {
+                cols,
+                item_width,
+                column_spacing,
+            } = GridMetrics::custom(&spacing, size.width as usize);
+
+            let mut grid = widget::grid();
+            let mut col = 0;
+            for preset in self.presets.iter().filter(|p| self.matches_query(&p.name)) {
+                if col >= cols {
+                    grid = grid.insert_row();
+                    col = 0;
+                }
+                let selected = self
+                    .selected_preset
+                    .as_ref()
+                    .is_some_and(|selected| selected.id == preset.id);
+                grid = grid.push(
+                    widget::column()
+                        .push(
+                            widget::button::custom(
+                                LayoutPreview::from(&preset.schema).view(&spacing, 130),
+                            )
+                            .on_press(Message::SelectPreset(preset.clone()))
+                            .class(cosmic::style::Button::Image)
+                            .selected(selected)
+                            .width(item_width as f32),
+                        )
+                        .push(widget::text(&preset.name))
+                        .push(widget::text::caption(preset.includes().join(", ")))
+                        .spacing(spacing.space_xxs)
+                        .align_x(Horizontal::Center),
+                );
+                col += 1;
+            }
+
+            widget::scrollable(
+                grid.column_spacing(column_spacing)
+                    .row_spacing(column_spacing),
+            )
+            .height(Length::Fill)
+            .width(Length::Fill)
+            .into()
+        })
+        .into()
+    }
+
+    fn available_layouts<'a>(&'a self) -> Element<'a, Message> {
+        if self.catalog_url.is_empty() {
+            return widget::text(fl!("no-layout-catalog")).into();
+        }
+
+        match self.status {
+            Status::Loading => widget::text(fl!("loading")).into(),
+            Status::Idle if self.available.is_empty() => {
+                widget::text(fl!("no-layouts-found")).into()
+            }
+            Status::Idle => widget::responsive(move |size| {
+                let spacing = cosmic::theme::spacing();
+                let GridMetrics {
+                    cols,
+                    item_width,
+                    column_spacing,
+                } = GridMetrics::custom(&spacing, size.width as usize);
+
+                let mut grid = widget::grid();
+                let mut col = 0;
+                for (index, available) in self
+                    .available
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, a)| self.matches_query(&a.layout.name))
+                {
+                    if col >= cols {
+                        grid = grid.insert_row();
+                        col = 0;
+                    }
+                    grid = grid.push(self.available_layout(index, available, item_width));
+                    col += 1;
+                }
+
+                widget::scrollable(
+                    grid.column_spacing(column_spacing)
+                        .row_spacing(column_spacing),
+                )
+                .height(Length::Fill)
+                .width(Length::Fill)
+                .into()
+            })
+            .into(),
+        }
+    }
+
+    fn available_layout<'a>(
+        &'a self,
+        index: usize,
+        available: &'a CatalogLayout,
+        item_width: usize,
+    ) -> Element<'a, Message> {
+        let spacing = cosmic::theme::spacing();
+        let installed = self.layouts.iter().any(|l| l.id == available.layout.id);
+
+        widget::column()
+            .push(LayoutPreview::from(&available.layout.schema).view(&spacing, 130))
+            .push(
+                widget::row()
+                    .push(
+                        widget::column()
+                            .push(widget::text(&available.layout.name))
+                            .push_maybe(
+                                available.author.as_ref().map(|author| {
+                                    widget::text::caption(fl!("by", author = author))
+                                }),
+                            )
+                            .push_maybe(available.downloads.map(|downloads| {
+                                widget::text::caption(fl!("downloads", downloads = downloads))
+                            }))
+                            .width(Length::Fill),
+                    )
+                    .push(widget::tooltip(
+                        widget::button::icon(icon_handle!("symbolic-link-symbolic", 14))
+                            .on_press_maybe(available.link.clone().map(Message::OpenLink)),
+                        widget::text(fl!("open-link")),
+                        widget::tooltip::Position::Bottom,
+                    ))
+                    .push(widget::tooltip(
+                        widget::button::icon(icon_handle!("folder-download-symbolic", 14))
+                            .on_press_maybe((!installed).then_some(Message::InstallLayout(index))),
+                        widget::text(fl!("install-layout")),
+                        widget::tooltip::Position::Bottom,
+                    ))
+                    .align_y(Alignment::Center)
+                    .spacing(spacing.space_xxs),
+            )
+            .spacing(spacing.space_xs)
+            .width(item_width as f32)
             .into()
     }
 
     pub fn update(&mut self, message: Message) -> Task<crate::app::message::Message> {
         match message {
-            Message::LoadLayouts(layouts) => {
+            Message::LoadLayouts(layouts, incompatible) => {
                 self.layouts = layouts;
+                self.incompatible = incompatible;
             }
+            Message::DeleteIncompatible(path) => match std::fs::remove_file(&path) {
+                Ok(_) => self.incompatible.retain(|i| i.path != path),
+                Err(e) => return Report::new(fl!("failed-delete-layout"), e).task(),
+            },
             Message::Select(layout) => {
                 self.selected_layout = Some(layout.clone());
             }
             Message::Apply => {
-                if let Some(layout) = &self.selected_layout {
-                    if let Err(e) = load_template(layout.schema.clone()) {
-                        eprintln!("Failed to load template: {}", e);
+                if let Some(layout) = self.selected_layout.take() {
+                    if let Err(e) = layout.apply() {
+                        // Kept selected so retrying applies it again.
+                        self.selected_layout = Some(layout);
+                        return Report::new(fl!("failed-apply-layout"), e)
+                            .retry(crate::app::message::Message::Layouts(Message::Apply))
+                            .task();
+                    } else {
+                        return Task::done(crate::app::message::Message::Settings(
+                            crate::app::message::SettingsMessage::AppliedLayout(Some(layout.id)),
+                        ));
                     }
-                    self.selected_layout = None;
                 }
             }
             Message::Delete => {
-                if let Some(layout) = self.selected_layout.clone() {
-                    let layouts_dir = dirs::data_local_dir()
-                        .unwrap()
-                        .join(App::APP_ID)
-                        .join("layouts");
-                    let file_path = layouts_dir
-                        .join(layout.id.to_string())
-                        .with_extension("ron");
-                    if file_path.exists() {
-                        match std::fs::remove_file(file_path) {
-                            Ok(_) => {
-                                self.selected_layout = None;
-                                self.layouts = self
-                                    .layouts
-                                    .clone()
-                                    .into_iter()
-                                    .filter(|l| l.id != layout.id)
-                                    .collect();
-                            }
-                            Err(e) => {
-                                eprintln!("Failed to delete layout: {}", e);
-                            }
-                        }
+                if let Some(layout) = self.selected_layout.take() {
+                    // Built-in layouts live in the binary, they can only be hidden.
+                    if !layout.custom {
+                        let mut hidden = self.hidden.clone();
+                        hidden.push(layout.id);
+                        return self.set_hidden(hidden);
+                    }
+                    match layout.delete() {
+                        Ok(_) => self.layouts.retain(|l| l.id != layout.id),
+                        Err(e) => return Report::new(fl!("failed-delete-layout"), e).task(),
                     }
                 }
             }
-            Message::Create(name, preview) => {
-                let layout = Layout::new(name, preview);
+            Message::Show(id) => {
+                let hidden = self.hidden.iter().copied().filter(|h| *h != id).collect();
+                return self.set_hidden(hidden);
+            }
+            Message::SetHiddenLayouts(hidden) => {
+                self.hidden = hidden;
+            }
+            Message::SaveCurrentLayout => {}
+            Message::Create(name, schema) => {
+                let layout = Layout::new(name, schema);
 
-                let layouts_dir = dirs::data_local_dir()
-                    .unwrap()
-                    .join(App::APP_ID)
-                    .join("layouts");
+                let Ok(layouts_dir) = Layout::dir() else {
+                    return Task::none();
+                };
 
                 let file_path = layouts_dir
                     .join(layout.id.to_string())
@@ -179,15 +572,215 @@ impl Layouts {
                     return Task::none();
                 }
 
-                match std::fs::write(&file_path, ron::to_string(&layout).unwrap()) {
-                    Ok(_) => match crate::app::pages::layouts::config::Layout::list() {
-                        Ok(layouts) => self.layouts = layouts,
-                        Err(e) => eprintln!("Failed to reload layouts: {e}"),
+                match layout.save() {
+                    Ok(_) => self.reload(),
+                    Err(e) => return Report::new(fl!("failed-save-layout"), e).task(),
+                };
+            }
+            Message::NewLayout => match PanelSchema::generate() {
+                Ok(panel_schema) => {
+                    self.editor = Some(LayoutEditor::new(Layout::new(
+                        fl!("new-layout"),
+                        Schema::Panel(panel_schema),
+                    )));
+                }
+                Err(e) => {
+                    return Report::new(fl!("failed-read-layout"), Error::Template(e.to_string()))
+                        .task();
+                }
+            },
+            Message::Edit => {
+                if let Some(layout) = self.selected_layout.clone().filter(|l| l.custom) {
+                    self.editor = Some(LayoutEditor::new(layout));
+                }
+            }
+            Message::Duplicate => {
+                if let Some(layout) = &self.selected_layout {
+                    self.editor = Some(LayoutEditor::new(layout.duplicate()));
+                }
+            }
+            Message::Editor(message) => {
+                if let Some(editor) = &mut self.editor {
+                    editor.update(message);
+                }
+            }
+            Message::SaveEdit => {
+                if let Some(editor) = &mut self.editor {
+                    editor.layout.version = Layout::VERSION;
+                    // The editor stays open on failure so the edits aren't lost.
+                    if let Err(e) = editor.layout.validate().and_then(|_| editor.layout.save()) {
+                        return Report::new(fl!("failed-save-layout"), e).task();
+                    }
+                    self.selected_layout = self.editor.take().map(|editor| editor.layout);
+                    self.reload();
+                }
+            }
+            Message::CancelEdit => {
+                self.editor = None;
+            }
+            Message::StartImport => {
+                return Task::perform(
+                    async {
+                        SelectedFiles::open_file()
+                            .modal(true)
+                            .filter(FileFilter::glob(FileFilter::new("ron"), "*.ron"))
+                            .send()
+                            .await?
+                            .response()
                     },
-                    Err(e) => {
-                        log::error!("Failed to write layout: {}", e);
+                    |res| match res {
+                        Ok(files) => crate::app::message::Message::Layouts(
+                            Message::ImportFilePickerResult(Arc::new(files)),
+                        ),
+                        Err(e) => crate::app::message::Message::Error(Report::new(
+                            fl!("failed-import-layout"),
+                            e,
+                        )),
+                    },
+                );
+            }
+            Message::ImportFilePickerResult(files) => {
+                match selected_path(&files).and_then(|path| Layout::import(&path, &self.layouts)) {
+                    Ok(layout) => {
+                        self.reload();
+                        self.selected_layout = Some(layout);
                     }
-                };
+                    Err(e) => return Report::new(fl!("failed-import-layout"), e).task(),
+                }
+            }
+            Message::StartExport => {
+                if let Some(layout) = self.selected_layout.clone() {
+                    let name = format!("{}.ron", layout.name);
+                    return Task::perform(
+                        async move {
+                            SelectedFiles::save_file()
+                                .modal(true)
+                                .current_name(name.as_str())
+                                .filter(FileFilter::glob(FileFilter::new("ron"), "*.ron"))
+                                .send()
+                                .await?
+                                .response()
+                        },
+                        move |res| match res {
+                            Ok(files) => crate::app::message::Message::Layouts(
+                                Message::ExportFilePickerResult(layout.clone(), Arc::new(files)),
+                            ),
+                            Err(e) => crate::app::message::Message::Error(Report::new(
+                                fl!("failed-export-layout"),
+                                e,
+                            )),
+                        },
+                    );
+                }
+            }
+            Message::ExportFilePickerResult(layout, files) => {
+                if let Err(e) = selected_path(&files).and_then(|path| layout.export(&path)) {
+                    return Report::new(fl!("failed-export-layout"), e).task();
+                }
+            }
+            Message::TabSelected(entity) => {
+                self.model.activate(entity);
+                if self.active_tab() == Tab::Available
+                    && self.available.is_empty()
+                    && matches!(self.status, Status::Idle)
+                {
+                    return self.update(Message::FetchAvailableLayouts);
+                }
+            }
+            Message::Query(query) => {
+                self.query = query;
+            }
+            // Only the cache is read here, as the address is written on every
+            // keystroke in the settings. The catalog is downloaded when the
+            // Available tab is opened or refreshed.
+            Message::SetCatalogUrl(url) => {
+                if url != self.catalog_url {
+                    self.available = cached_layouts(&url).unwrap_or_default();
+                    self.catalog_url = url;
+                }
+            }
+            Message::FetchAvailableLayouts => {
+                if self.catalog_url.is_empty() {
+                    return Task::none();
+                }
+                self.status = Status::Loading;
+                let url = self.catalog_url.clone();
+                return Task::perform(
+                    async move {
+                        let layouts = catalog::download_layouts(url.clone()).await;
+                        (url, layouts)
+                    },
+                    |(url, res)| {
+                        crate::app::message::Message::Layouts(match res {
+                            Ok(layouts) => Message::SetAvailableLayouts(url, layouts),
+                            Err(e) => Message::FetchFailed(format!("{e}")),
+                        })
+                    },
+                );
+            }
+            Message::SetAvailableLayouts(url, available) => {
+                self.status = Status::Idle;
+
+                if let Err(e) = catalog::cache_layouts(&url, &available) {
+                    log::error!("can't cache layouts: {e}");
+                }
+
+                // The address may have changed while downloading.
+                if url == self.catalog_url {
+                    self.available = available;
+                }
+            }
+            Message::FetchFailed(e) => {
+                self.status = Status::Idle;
+                return Report::new(fl!("failed-fetch-layouts"), Error::Catalog(e))
+                    .retry(crate::app::message::Message::Layouts(
+                        Message::FetchAvailableLayouts,
+                    ))
+                    .task();
+            }
+            Message::InstallLayout(index) => {
+                if let Some(available) = self.available.get(index) {
+                    match available.layout.clone().install(&self.layouts) {
+                        Ok(layout) => {
+                            self.reload();
+                            self.selected_layout = Some(layout);
+                        }
+                        Err(e) => return Report::new(fl!("failed-install-layout"), e).task(),
+                    }
+                }
+            }
+            Message::OpenLink(link) => {
+                if let Err(e) = open::that_detached(link) {
+                    return Report::new(fl!("failed-open-link"), e).task();
+                }
+            }
+            Message::SelectPreset(preset) => {
+                self.selected_preset = Some(preset);
+            }
+            Message::ApplyPreset => {
+                if let Some(preset) = self.selected_preset.take()
+                    && let Err(e) = preset.apply()
+                {
+                    return Report::new(fl!("failed-apply-preset"), e).task();
+                }
+            }
+            Message::DeletePreset => {
+                if let Some(preset) = self.selected_preset.take() {
+                    match preset.delete() {
+                        Ok(_) => self.presets.retain(|p| p.id != preset.id),
+                        Err(e) => return Report::new(fl!("failed-delete-preset"), e).task(),
+                    }
+                }
+            }
+            Message::SaveCurrentPreset => {}
+            Message::CreatePreset(name) => {
+                match Preset::current(name).and_then(|preset| preset.save().map(|_| preset)) {
+                    Ok(preset) => {
+                        self.reload_presets();
+                        self.selected_preset = Some(preset);
+                    }
+                    Err(e) => return Report::new(fl!("failed-save-preset"), e).task(),
+                }
             }
         }
         Task::none()
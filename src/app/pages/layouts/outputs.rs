@@ -0,0 +1,88 @@
+use cosmic_ext_config_templates::Schema;
+use cosmic_panel_config::{CosmicPanelConfig, CosmicPanelOuput};
+
+/// Names of the connected outputs, such as `eDP-1` or `HDMI-A-1`.
+///
+/// Read from the DRM connectors, which use the same names as the compositor.
+/// Empty when they can't be read, in which case placements are kept as they are.
+pub fn connected() -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir("/sys/class/drm") else {
+        return vec![];
+    };
+
+    let mut outputs: Vec<String> = entries
+        .flatten()
+        .filter_map(|entry| {
+            // Connectors are named `card<N>-<output>`, the cards themselves have no dash.
+            let name = entry.file_name().to_string_lossy().to_string();
+            let (_, output) = name.split_once('-')?;
+            let status = std::fs::read_to_string(entry.path().join("status")).ok()?;
+            (status.trim() == "connected").then(|| output.to_string())
+        })
+        .collect();
+    outputs.sort();
+    outputs.dedup();
+    outputs
+}
+
+/// The outputs named by the panels of a layout, in order of appearance.
+pub fn named(configs: &[CosmicPanelConfig]) -> Vec<String> {
+    let mut outputs: Vec<String> = Vec::new();
+    for config in configs {
+        if let CosmicPanelOuput::Name(name) = &config.output
+            && !outputs.contains(name)
+        {
+            outputs.push(name.clone());
+        }
+    }
+    outputs
+}
+
+/// Whether a panel is drawn on an output of a layout that names `outputs`.
+///
+/// `None` stands for every output the layout doesn't name. Panels on the
+/// active output are drawn on the first named output, which is treated as
+/// the primary one.
+pub fn shown_on(config: &CosmicPanelConfig, output: Option<&str>, outputs: &[String]) -> bool {
+    match (&config.output, output) {
+        (CosmicPanelOuput::All, _) => true,
+        (CosmicPanelOuput::Active, None) => outputs.is_empty(),
+        (CosmicPanelOuput::Active, Some(output)) => {
+            outputs.first().map(String::as_str) == Some(output)
+        }
+        (CosmicPanelOuput::Name(name), Some(output)) => name == output,
+        (CosmicPanelOuput::Name(_), None) => false,
+    }
+}
+
+/// Moves panels placed on disconnected outputs to the active output.
+///
+/// Layouts made on another machine would otherwise apply panels that never
+/// show up. Nothing changes when the connected outputs are unknown.
+pub fn resolve(configs: &mut [CosmicPanelConfig], connected: &[String]) {
+    if connected.is_empty() {
+        return;
+    }
+
+    for config in configs {
+        if let CosmicPanelOuput::Name(name) = &config.output
+            && !connected.contains(name)
+        {
+            log::warn!(
+                "Output {name} of panel {} is not connected, using the active output",
+                config.name
+            );
+            config.output = CosmicPanelOuput::Active;
+        }
+    }
+}
+
+/// Prepares a layout schema for this machine before it is applied, see [`resolve`].
+pub fn resolve_schema(mut schema: Schema) -> Schema {
+    match &mut schema {
+        Schema::Panel(panel_schema) => {
+            resolve(&mut panel_schema.panel_config.config_list, &connected())
+        }
+    }
+    schema
+}
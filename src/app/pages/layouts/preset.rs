@@ -0,0 +1,328 @@
+use std::path::PathBuf;
+
+use cosmic::cosmic_config::{self, ConfigSet, CosmicConfigEntry};
+use cosmic::cosmic_theme::{Theme, ThemeBuilder};
+use cosmic_ext_config_templates::{Schema, load_template, panel::PanelSchema};
+use cosmic_settings_config::{Shortcuts, shortcuts};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{config::Layout, outputs};
+use crate::{
+    Error,
+    app::{
+        App,
+        core::{
+            config::TweaksConfig,
+            versioned::{self, Incompatible, Versioned},
+        },
+        pages::{
+            color_schemes::{self, ColorScheme},
+            panel::config::{CosmicPanelButtonConfig, IndividualConfig, Override},
+            shortcuts::{
+                ShortcutsGroup, custom_shortcuts,
+                scheme::{self, AppliedScheme},
+            },
+        },
+    },
+    fl,
+};
+
+const PANEL_BUTTON_CONFIG_ID: &str = "com.system76.CosmicPanelButton";
+
+/// A layout bundled with the color scheme, shortcuts and panel button
+/// overrides that complete it.
+///
+/// Everything but the schema is optional, missing parts are left untouched
+/// when the preset is applied.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Preset {
+    #[serde(default)]
+    pub version: u32,
+    pub id: Uuid,
+    pub name: String,
+    pub custom: bool,
+    pub schema: Schema,
+    #[serde(default)]
+    pub color_scheme: Option<ColorScheme>,
+    #[serde(default)]
+    pub shortcuts: Option<ShortcutsGroup>,
+    #[serde(default)]
+    pub panel_buttons: Option<CosmicPanelButtonConfig>,
+}
+
+/// The parts of the desktop Tweaks writes, captured so a failed apply can be
+/// undone and stored whole by profiles.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DesktopState {
+    pub schema: Schema,
+    pub theme: Option<ThemeBuilder>,
+    pub shortcuts: Shortcuts,
+    pub shortcut_schemes: Vec<AppliedScheme>,
+    pub panel_buttons: CosmicPanelButtonConfig,
+}
+
+impl PartialEq for DesktopState {
+    /// Schemas don't implement `PartialEq`, they are compared through their
+    /// serialized form.
+    fn eq(&self, other: &Self) -> bool {
+        self.theme == other.theme
+            && self.shortcuts == other.shortcuts
+            && self.shortcut_schemes == other.shortcut_schemes
+            && self.panel_buttons == other.panel_buttons
+            && same_schema(&self.schema, &other.schema)
+    }
+}
+
+impl Preset {
+    /// Captures the current panels, color scheme, shortcut scheme and panel
+    /// buttons.
+    ///
+    /// A preset holds one shortcut scheme, the one applied last is kept.
+    pub fn current(name: String) -> Result<Self, Error> {
+        let schema = current_schema()?;
+        let color_scheme = color_schemes::get_current_theme()
+            .map(|builder| ColorScheme::new(name.clone(), builder))
+            .map_err(|e| log::warn!("Preset {name} will not include a color scheme: {e}"))
+            .ok();
+
+        Ok(Self {
+            version: Self::VERSION,
+            id: Uuid::new_v4(),
+            name,
+            custom: true,
+            schema,
+            color_scheme,
+            shortcuts: TweaksConfig::new()
+                .shortcut_schemes
+                .last()
+                .map(|applied| applied.group.clone()),
+            panel_buttons: Some(current_panel_buttons(&panel_button_config()?)),
+        })
+    }
+
+    /// The presets shipped with Tweaks, built from the bundled layouts.
+    pub fn bundled() -> Vec<Self> {
+        let icons = |names: &[&str]| CosmicPanelButtonConfig {
+            configs: names
+                .iter()
+                .map(|name| {
+                    (
+                        name.to_string(),
+                        IndividualConfig {
+                            force_presentation: Some(Override::Icon),
+                        },
+                    )
+                })
+                .collect(),
+        };
+
+        Layout::bundled_by_slug()
+            .into_iter()
+            .filter_map(|(slug, layout)| {
+                let (shortcuts, panel_buttons) = match slug {
+                    "mac" => (None, icons(&["Dock"])),
+                    "windows" => (Some(ShortcutsGroup::Windows), icons(&["Panel"])),
+                    "ubuntu" => (None, icons(&["Dock"])),
+                    _ => return None,
+                };
+                Some(Self {
+                    version: Self::VERSION,
+                    id: layout.id,
+                    name: layout.name,
+                    custom: false,
+                    schema: layout.schema,
+                    color_scheme: None,
+                    shortcuts,
+                    panel_buttons: Some(panel_buttons),
+                })
+            })
+            .collect()
+    }
+
+    pub fn dir() -> Result<PathBuf, Error> {
+        dirs::data_local_dir()
+            .map(|path| path.join(App::APP_ID).join("presets"))
+            .ok_or(Error::DataDirNotFound)
+    }
+
+    pub fn list() -> Result<(Vec<Preset>, Vec<Incompatible>), Error> {
+        Ok(versioned::read_dir(&Self::dir()?))
+    }
+
+    pub fn path(&self) -> Result<PathBuf, Error> {
+        Ok(Self::dir()?.join(self.id.to_string()).with_extension("ron"))
+    }
+
+    pub fn save(&self) -> Result<(), Error> {
+        let dir = Self::dir()?;
+        if !dir.exists() {
+            std::fs::create_dir_all(&dir)?;
+        }
+        let data = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        std::fs::write(self.path()?, data)?;
+        Ok(())
+    }
+
+    pub fn delete(&self) -> Result<(), Error> {
+        std::fs::remove_file(self.path()?)?;
+        Ok(())
+    }
+
+    /// Applies every part of the preset, or none of them.
+    ///
+    /// The current desktop is captured first and written back if any part
+    /// fails, so a preset never leaves the panels and the theme out of sync.
+    pub fn apply(&self) -> Result<(), Error> {
+        DesktopState::rollback_on_error(&format!("preset {}", self.name), || self.write())
+    }
+
+    fn write(&self) -> Result<(), Error> {
+        load_template(outputs::resolve_schema(self.schema.clone()))
+            .map_err(|e| Error::Apply(format!("layout: {e}")))?;
+
+        if let Some(color_scheme) = &self.color_scheme {
+            color_schemes::apply_theme(&color_scheme.theme)
+                .map_err(|e| Error::Apply(format!("color scheme: {e}")))?;
+        }
+
+        if let Some(group) = &self.shortcuts {
+            let scheme = scheme::Scheme::load(group)
+                .ok_or_else(|| Error::Apply("shortcuts: the group no longer exists".into()))?;
+            let config = shortcuts::context()?;
+            let mut shortcuts = custom_shortcuts(&config);
+            let mut tweaks = TweaksConfig::new();
+            let mut applied = tweaks.shortcut_schemes.clone();
+            scheme::apply(&scheme, &mut shortcuts, &mut applied, &[]);
+            config.set("custom", shortcuts)?;
+            tweaks.set_shortcut_schemes(&TweaksConfig::config(), applied)?;
+        }
+
+        if let Some(panel_buttons) = &self.panel_buttons {
+            panel_buttons.write_entry(&panel_button_config()?)?;
+        }
+
+        Ok(())
+    }
+
+    /// Names the parts of the desktop the preset changes.
+    pub fn includes(&self) -> Vec<String> {
+        let mut includes = vec![fl!("layout")];
+        if let Some(color_scheme) = &self.color_scheme {
+            includes.push(color_scheme.name.clone());
+        }
+        if self.shortcuts.is_some() {
+            includes.push(fl!("shortcuts"));
+        }
+        if self.panel_buttons.is_some() {
+            includes.push(fl!("panel-buttons"));
+        }
+        includes
+    }
+}
+
+impl DesktopState {
+    pub fn capture() -> Result<Self, Error> {
+        let theme = color_schemes::get_current_theme()
+            .map_err(|e| log::warn!("The current color scheme can't be restored: {e}"))
+            .ok();
+
+        Ok(Self {
+            schema: current_schema()?,
+            theme,
+            shortcuts: custom_shortcuts(&shortcuts::context()?),
+            shortcut_schemes: TweaksConfig::new().shortcut_schemes,
+            panel_buttons: current_panel_buttons(&panel_button_config()?),
+        })
+    }
+
+    /// Runs `write`, putting the captured desktop back if it fails.
+    ///
+    /// `what` names the change in the log, e.g. "preset Mac".
+    pub fn rollback_on_error(
+        what: &str,
+        write: impl FnOnce() -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        let backup = Self::capture()?;
+
+        if let Err(e) = write() {
+            log::error!("Failed to apply {what}, restoring the desktop: {e}");
+            if let Err(e) = backup.restore() {
+                log::error!("Failed to restore the desktop: {e}");
+            }
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    /// Writes everything back, continuing past failures to restore as much as possible.
+    pub fn restore(self) -> Result<(), Error> {
+        let mut result =
+            load_template(self.schema).map_err(|e| Error::Apply(format!("layout: {e}")));
+
+        if let Some(builder) = self.theme {
+            let theme: Theme = builder.build();
+            if let Err(e) = color_schemes::apply_theme(&theme) {
+                result = result.and(Err(Error::Apply(format!("color scheme: {e}"))));
+            }
+        }
+
+        let shortcuts = shortcuts::context()
+            .and_then(|config| config.set("custom", self.shortcuts))
+            .map_err(Error::from);
+        result = result.and(shortcuts);
+
+        let shortcut_schemes = TweaksConfig::new()
+            .set_shortcut_schemes(&TweaksConfig::config(), self.shortcut_schemes)
+            .map(|_| ())
+            .map_err(Error::from);
+        result = result.and(shortcut_schemes);
+
+        let panel_buttons =
+            panel_button_config().and_then(|config| Ok(self.panel_buttons.write_entry(&config)?));
+        result.and(panel_buttons)
+    }
+}
+
+impl Versioned for Preset {
+    const VERSION: u32 = 1;
+
+    fn version(&self) -> u32 {
+        self.version
+    }
+
+    fn migrate(_contents: &str, version: u32) -> Result<Self, Error> {
+        Err(Error::UnsupportedVersion(version))
+    }
+}
+
+/// Compares schemas through their serialized form, as they don't implement
+/// `PartialEq`.
+pub fn same_schema(a: &Schema, b: &Schema) -> bool {
+    ron::ser::to_string(a).ok() == ron::ser::to_string(b).ok()
+}
+
+/// The schema of the panels in use.
+pub fn current_schema() -> Result<Schema, Error> {
+    PanelSchema::generate()
+        .map(Schema::Panel)
+        .map_err(|e| Error::Apply(format!("layout: {e}")))
+}
+
+fn panel_button_config() -> Result<cosmic_config::Config, Error> {
+    Ok(cosmic_config::Config::new(
+        PANEL_BUTTON_CONFIG_ID,
+        CosmicPanelButtonConfig::VERSION,
+    )?)
+}
+
+fn current_panel_buttons(config: &cosmic_config::Config) -> CosmicPanelButtonConfig {
+    match CosmicPanelButtonConfig::get_entry(config) {
+        Ok(config) => config,
+        Err((errs, config)) => {
+            log::error!("errors loading config for cosmic panel button: {:?}", errs);
+            config
+        }
+    }
+}
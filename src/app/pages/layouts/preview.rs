@@ -6,27 +6,37 @@ use cosmic::{
     },
     widget::{self, horizontal_space, vertical_space},
 };
-use serde::{Deserialize, Serialize};
+use cosmic_ext_config_templates::Schema;
+use cosmic_panel_config::{CosmicPanelConfig, PanelAnchor};
 
+use super::outputs;
+use crate::app::pages::panel::size;
 use crate::fl;
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+/// The applet that turns a panel into a dock-like bar of app icons.
+const APP_LIST: &str = "com.system76.CosmicAppList";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct LayoutPreview {
     pub panel: PanelProperties,
     pub dock: PanelProperties,
-    pub dock_icons: u8,
-    pub show_window: bool,
+    /// The applets in the center of the dock, each drawn as one square.
+    ///
+    /// The app list counts as a single applet, its favorites are not part of
+    /// the layout.
+    pub dock_applets: u8,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct PanelProperties {
     pub position: Position,
     pub extend: bool,
     pub hidden: bool,
     pub size: usize,
+    pub autohide: bool,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Position {
     Top,
     Bottom,
@@ -40,30 +50,32 @@ impl LayoutPreview {
         spacing: &cosmic::cosmic_theme::Spacing,
         height: u16,
     ) -> Element<'a, Message> {
+        let panel_size = self.panel.drawn_size() as f32;
+        let dock_size = self.dock.drawn_size() as f32;
         let column = widget::column().height(height).width(Length::Fill);
         let row = widget::row().height(height);
 
         let panel = widget::container(match self.panel.position {
             Position::Top | Position::Bottom => Element::from(
                 widget::row()
-                    .push(Self::square(self.panel.size as f32 - 5.0))
+                    .push(Self::square(panel_size - 5.0))
                     .push_maybe(if self.panel.extend {
                         Some(horizontal_space())
                     } else {
                         None
                     })
-                    .push(Self::square(self.panel.size as f32 - 5.0))
+                    .push(Self::square(panel_size - 5.0))
                     .spacing(spacing.space_xxs)
                     .align_y(Vertical::Center),
             ),
             Position::Left | Position::Right => widget::column()
-                .push(Self::square(self.panel.size as f32 - 5.0))
+                .push(Self::square(panel_size - 5.0))
                 .push_maybe(if self.panel.extend {
                     Some(vertical_space())
                 } else {
                     None
                 })
-                .push(Self::square(self.panel.size as f32 - 5.0))
+                .push(Self::square(panel_size - 5.0))
                 .spacing(spacing.space_xxs)
                 .align_x(Horizontal::Center)
                 .into(),
@@ -81,8 +93,8 @@ impl LayoutPreview {
                     Length::Shrink
                 };
 
-                let icons: Vec<Element<_>> = (0..self.dock_icons)
-                    .map(|_| Self::square::<Message>(self.dock.size as f32 - 5.0).into())
+                let icons: Vec<Element<_>> = (0..self.dock_applets)
+                    .map(|_| Self::square::<Message>(dock_size - 5.0).into())
                     .collect();
 
                 let icons: Element<_> =
@@ -139,22 +151,22 @@ impl LayoutPreview {
                 };
                 match self.panel.position {
                     Position::Top => column
-                        .push(panel.width(extend_panel).height(self.panel.size as f32))
+                        .push(panel.width(extend_panel).height(panel_size))
                         .align_x(Horizontal::Center)
                         .into(),
                     Position::Bottom => column
                         .push(vertical_space())
-                        .push(panel.width(extend_panel).height(self.panel.size as f32))
+                        .push(panel.width(extend_panel).height(panel_size))
                         .align_x(Horizontal::Center)
                         .into(),
                     Position::Left => row
-                        .push(panel.width(self.panel.size as f32).height(extend_panel))
+                        .push(panel.width(panel_size).height(extend_panel))
                         .push(horizontal_space())
                         .align_y(Vertical::Center)
                         .into(),
                     Position::Right => row
                         .push(horizontal_space())
-                        .push(panel.width(self.panel.size as f32).height(extend_panel))
+                        .push(panel.width(panel_size).height(extend_panel))
                         .align_y(Vertical::Center)
                         .into(),
                 }
@@ -176,8 +188,8 @@ impl LayoutPreview {
                     Length::Shrink
                 };
 
-                let icons: Vec<Element<_>> = (0..self.dock_icons)
-                    .map(|_| Self::square::<Message>(self.dock.size as f32 - 5.0).into())
+                let icons: Vec<Element<_>> = (0..self.dock_applets)
+                    .map(|_| Self::square::<Message>(dock_size - 5.0).into())
                     .collect();
 
                 let icons: Element<_> =
@@ -205,20 +217,20 @@ impl LayoutPreview {
 
                 match (self.panel.position, self.dock.position) {
                     (Position::Top, Position::Top) => column
-                        .push(panel.width(extend_panel).height(self.panel.size as f32))
+                        .push(panel.width(extend_panel).height(panel_size))
                         .push(horizontal_space())
                         .push(dock.width(extend_dock))
                         .align_x(Horizontal::Center)
                         .into(),
                     (Position::Top, Position::Bottom) => column
-                        .push(panel.width(extend_panel).height(self.panel.size as f32))
+                        .push(panel.width(extend_panel).height(panel_size))
                         .push(vertical_space())
                         .push(horizontal_space())
                         .push(dock.width(extend_dock))
                         .align_x(Horizontal::Center)
                         .into(),
                     (Position::Top, Position::Left) => column
-                        .push(panel.width(extend_panel).height(self.panel.size as f32))
+                        .push(panel.width(extend_panel).height(panel_size))
                         .push(
                             widget::row()
                                 .push(dock.height(extend_dock))
@@ -227,7 +239,7 @@ impl LayoutPreview {
                         .align_x(Horizontal::Center)
                         .into(),
                     (Position::Top, Position::Right) => column
-                        .push(panel.width(extend_panel).height(self.panel.size as f32))
+                        .push(panel.width(extend_panel).height(panel_size))
                         .push(
                             widget::row()
                                 .push(horizontal_space())
@@ -240,13 +252,13 @@ impl LayoutPreview {
                         .push(dock.width(extend_dock))
                         .push(vertical_space())
                         .push(horizontal_space())
-                        .push(panel.width(extend_panel).height(self.panel.size as f32))
+                        .push(panel.width(extend_panel).height(panel_size))
                         .align_x(Horizontal::Center)
                         .into(),
                     (Position::Bottom, Position::Bottom) => column
                         .push(vertical_space())
                         .push(horizontal_space())
-                        .push(panel.width(extend_panel).height(self.panel.size as f32))
+                        .push(panel.width(extend_panel).height(panel_size))
                         .push(dock.width(extend_dock))
                         .align_x(Horizontal::Center)
                         .into(),
@@ -256,7 +268,7 @@ impl LayoutPreview {
                             widget::column()
                                 .push(vertical_space())
                                 .push(horizontal_space())
-                                .push(panel.width(extend_panel).height(self.panel.size as f32))
+                                .push(panel.width(extend_panel).height(panel_size))
                                 .align_x(Horizontal::Center),
                         )
                         .align_y(Vertical::Center)
@@ -270,7 +282,7 @@ impl LayoutPreview {
                         .push(
                             widget::column()
                                 .push(vertical_space())
-                                .push(panel.width(extend_panel).height(self.panel.size as f32)),
+                                .push(panel.width(extend_panel).height(panel_size)),
                         )
                         .push_maybe(if self.panel.extend {
                             None
@@ -281,7 +293,7 @@ impl LayoutPreview {
                         .align_y(Vertical::Center)
                         .into(),
                     (Position::Left, Position::Top) => row
-                        .push(panel.width(self.panel.size as f32).height(extend_panel))
+                        .push(panel.width(panel_size).height(extend_panel))
                         .push_maybe(if self.dock.extend {
                             None
                         } else {
@@ -301,7 +313,7 @@ impl LayoutPreview {
                         .align_y(Vertical::Center)
                         .into(),
                     (Position::Left, Position::Bottom) => row
-                        .push(panel.width(self.panel.size as f32).height(extend_panel))
+                        .push(panel.width(panel_size).height(extend_panel))
                         .push_maybe(if self.dock.extend {
                             None
                         } else {
@@ -322,13 +334,13 @@ impl LayoutPreview {
                         .align_y(Vertical::Center)
                         .into(),
                     (Position::Left, Position::Left) => row
-                        .push(panel.width(self.panel.size as f32).height(extend_panel))
+                        .push(panel.width(panel_size).height(extend_panel))
                         .push(dock.height(extend_dock))
                         .push(horizontal_space())
                         .align_y(Vertical::Center)
                         .into(),
                     (Position::Left, Position::Right) => row
-                        .push(panel.width(self.panel.size as f32).height(extend_panel))
+                        .push(panel.width(panel_size).height(extend_panel))
                         .push(horizontal_space())
                         .push(dock.height(extend_dock))
                         .align_y(Vertical::Center)
@@ -350,7 +362,7 @@ impl LayoutPreview {
                         } else {
                             Some(horizontal_space())
                         })
-                        .push(panel.width(self.panel.size as f32).height(extend_panel))
+                        .push(panel.width(panel_size).height(extend_panel))
                         .align_y(Vertical::Center)
                         .into(),
                     (Position::Right, Position::Bottom) => row
@@ -370,19 +382,19 @@ impl LayoutPreview {
                         } else {
                             Some(horizontal_space())
                         })
-                        .push(panel.width(self.panel.size as f32).height(extend_panel))
+                        .push(panel.width(panel_size).height(extend_panel))
                         .align_y(Vertical::Center)
                         .into(),
                     (Position::Right, Position::Left) => row
                         .push(dock.height(extend_dock))
                         .push(horizontal_space())
-                        .push(panel.width(self.panel.size as f32).height(extend_panel))
+                        .push(panel.width(panel_size).height(extend_panel))
                         .align_y(Vertical::Center)
                         .into(),
                     (Position::Right, Position::Right) => row
                         .push(horizontal_space())
                         .push(dock.height(extend_dock))
-                        .push(panel.width(self.panel.size as f32).height(extend_panel))
+                        .push(panel.width(panel_size).height(extend_panel))
                         .align_y(Vertical::Center)
                         .into(),
                 }
@@ -404,6 +416,97 @@ impl LayoutPreview {
     }
 }
 
+impl From<&Schema> for LayoutPreview {
+    /// The preview of the primary output, see [`LayoutPreview::per_output`].
+    fn from(schema: &Schema) -> Self {
+        Self::per_output(schema)
+            .into_iter()
+            .next()
+            .map(|(_, preview)| preview)
+            .unwrap_or_default()
+    }
+}
+
+impl LayoutPreview {
+    /// Builds one preview per output named by the layout.
+    ///
+    /// Layouts that don't name outputs get a single preview labelled `None`.
+    /// Otherwise the first named output is treated as the primary one, and
+    /// panels shown on every output add a `None` preview for the remaining ones.
+    pub fn per_output(schema: &Schema) -> Vec<(Option<String>, Self)> {
+        let configs = match schema {
+            Schema::Panel(panel_schema) => &panel_schema.panel_config.config_list,
+        };
+        let named = outputs::named(configs);
+        let preview = |output: Option<&str>| {
+            let shown: Vec<&CosmicPanelConfig> = configs
+                .iter()
+                .filter(|config| outputs::shown_on(config, output, &named))
+                .collect();
+            Self::from_configs(&shown)
+        };
+
+        let mut previews: Vec<(Option<String>, Self)> = named
+            .iter()
+            .map(|output| (Some(output.clone()), preview(Some(output))))
+            .collect();
+        if named.is_empty()
+            || configs
+                .iter()
+                .any(|config| outputs::shown_on(config, None, &named))
+        {
+            previews.push((None, preview(None)));
+        }
+        previews
+    }
+
+    /// Builds a preview from the panel configs shown on one output.
+    ///
+    /// The config named "Dock", or else the one hosting the app list, is drawn
+    /// as the dock. The config named "Panel", or else the first remaining one,
+    /// is drawn as the panel.
+    pub fn from_configs(configs: &[&CosmicPanelConfig]) -> Self {
+        let dock = configs
+            .iter()
+            .copied()
+            .find(|config| config.name == "Dock")
+            .or_else(|| {
+                configs.iter().copied().find(|config| {
+                    config
+                        .plugins_center
+                        .as_ref()
+                        .is_some_and(|center| center.iter().any(|applet| applet == APP_LIST))
+                })
+            });
+        let dock_name = dock.map(|config| config.name.as_str());
+        let panel = configs
+            .iter()
+            .copied()
+            .find(|config| config.name == "Panel" && dock_name != Some("Panel"))
+            .or_else(|| {
+                configs
+                    .iter()
+                    .copied()
+                    .find(|config| dock_name != Some(config.name.as_str()))
+            });
+
+        let dock_applets = dock
+            .and_then(|config| config.plugins_center.as_ref())
+            .map(|center| center.len().min(20) as u8)
+            .unwrap_or_default();
+
+        Self {
+            panel: panel
+                .map(PanelProperties::from)
+                .unwrap_or_else(|| PanelProperties::hidden(Position::Top)),
+            dock: dock
+                .map(PanelProperties::from)
+                .unwrap_or_else(|| PanelProperties::hidden(Position::Bottom)),
+            dock_applets,
+        }
+    }
+}
+
 impl PanelProperties {
     pub fn new(position: Position, extend: bool, hidden: bool, size: usize) -> Self {
         Self {
@@ -411,6 +514,46 @@ impl PanelProperties {
             extend,
             hidden,
             size,
+            autohide: false,
+        }
+    }
+
+    pub fn hidden(position: Position) -> Self {
+        Self::new(position, false, true, 10)
+    }
+
+    /// The thickness used when drawing, autohidden panels are drawn thinner.
+    fn drawn_size(&self) -> usize {
+        if self.autohide {
+            (self.size / 2).max(6)
+        } else {
+            self.size
+        }
+    }
+}
+
+impl From<&CosmicPanelConfig> for PanelProperties {
+    fn from(config: &CosmicPanelConfig) -> Self {
+        // Scale the real thickness (16 to 112 px) down to the thumbnail.
+        let size = (size::to_u32(config.size.clone()) / 3).clamp(10, 20) as usize;
+
+        Self {
+            position: Position::from(config.anchor),
+            extend: config.expand_to_edges,
+            hidden: false,
+            size,
+            autohide: config.autohide.is_some(),
+        }
+    }
+}
+
+impl From<PanelAnchor> for Position {
+    fn from(anchor: PanelAnchor) -> Self {
+        match anchor {
+            PanelAnchor::Top => Position::Top,
+            PanelAnchor::Bottom => Position::Bottom,
+            PanelAnchor::Left => Position::Left,
+            PanelAnchor::Right => Position::Right,
         }
     }
 }
@@ -420,8 +563,7 @@ impl Default for LayoutPreview {
         Self {
             panel: PanelProperties::new(Position::Top, true, false, 20),
             dock: PanelProperties::new(Position::Bottom, true, false, 20),
-            dock_icons: 6,
-            show_window: true,
+            dock_applets: 6,
         }
     }
 }
@@ -1,13 +1,17 @@
+pub mod applets;
 pub mod color_schemes;
 pub mod dock;
 pub mod layouts;
 pub mod panel;
+pub mod panels;
 pub mod shortcuts;
 pub mod snapshots;
 
+pub use applets::Applets;
 pub use color_schemes::ColorSchemes;
 pub use dock::Dock;
 pub use layouts::Layouts;
 pub use panel::Panel;
+pub use panels::Panels;
 pub use shortcuts::ShortcutsPage;
 pub use snapshots::Snapshots;
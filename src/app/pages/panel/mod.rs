@@ -3,9 +3,18 @@ use cosmic::{
     cosmic_config::{self, Config, CosmicConfigEntry},
     widget,
 };
-use cosmic_panel_config::{AutoHide, CosmicPanelConfig};
+use cosmic_panel_config::{
+    AutoHide, CosmicPanelBackground, CosmicPanelConfig, CosmicPanelOuput, KeyboardInteractivity,
+    Layer, PanelAnchor, PanelSize,
+};
 use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
 
+use crate::app::core::style;
+use crate::app::page::Page;
+use crate::app::pages::layouts::outputs;
+use crate::app::report::Report;
+use crate::app::search::{SearchItem, Target};
 use crate::{fl, icon};
 
 use config::{CosmicPanelButtonConfig, IndividualConfig, Override};
@@ -15,9 +24,14 @@ pub mod size;
 
 #[derive(Debug)]
 pub struct Panel {
+    /// The entry of `CosmicPanel::entries` this page edits.
+    pub name: String,
+    /// The section title, translated for the bars COSMIC ships with.
+    title: String,
     pub panel_helper: Option<Config>,
     pub panel_config: Option<CosmicPanelConfig>,
     pub padding: u32,
+    padding_range: RangeInclusive<u32>,
     pub margin: u16,
     pub spacing: u32,
     pub border_radius: u32,
@@ -26,11 +40,61 @@ pub struct Panel {
     pub cosmic_panel_config_helper: Option<Config>,
     pub cosmic_panel_button_config: CosmicPanelButtonConfig,
     pub cosmic_panel_button_config_helper: Option<Config>,
-    pub force_icons: bool,
+    /// How the buttons of this panel are presented, `None` leaving it to the applets.
+    pub presentation: Option<Override>,
     panel_size: cosmic_panel_config::PanelSize,
     autohide: AutoHide,
+    outputs: Vec<CosmicPanelOuput>,
+    labels: Labels,
+    /// The setting the global search jumped to.
+    highlight: Option<&'static str>,
 }
 
+/// Labels of the dropdowns, kept here because dropdowns borrow their options.
+#[derive(Debug)]
+struct Labels {
+    anchors: Vec<String>,
+    layers: Vec<String>,
+    interactivity: Vec<String>,
+    backgrounds: Vec<String>,
+    sizes: Vec<String>,
+    outputs: Vec<String>,
+    presentations: Vec<String>,
+}
+
+/// Button presentations, after "default" which leaves it to the applets.
+const PRESENTATIONS: [Override; 2] = [Override::Icon, Override::Text];
+
+const ANCHORS: [PanelAnchor; 4] = [
+    PanelAnchor::Top,
+    PanelAnchor::Bottom,
+    PanelAnchor::Left,
+    PanelAnchor::Right,
+];
+
+const LAYERS: [Layer; 4] = [Layer::Background, Layer::Bottom, Layer::Top, Layer::Overlay];
+
+const INTERACTIVITY: [KeyboardInteractivity; 3] = [
+    KeyboardInteractivity::None,
+    KeyboardInteractivity::Exclusive,
+    KeyboardInteractivity::OnDemand,
+];
+
+const BACKGROUNDS: [CosmicPanelBackground; 3] = [
+    CosmicPanelBackground::ThemeDefault,
+    CosmicPanelBackground::Dark,
+    CosmicPanelBackground::Light,
+];
+
+/// Sizes offered for the wings and the center, after "same as the panel".
+const SECTION_SIZES: [PanelSize; 5] = [
+    PanelSize::XS,
+    PanelSize::S,
+    PanelSize::M,
+    PanelSize::L,
+    PanelSize::XL,
+];
+
 #[derive(
     Debug,
     Clone,
@@ -47,10 +111,16 @@ pub struct CosmicPanel {
 
 impl Default for Panel {
     fn default() -> Self {
-        let panel_helper = CosmicPanelConfig::cosmic_config("Panel").ok();
+        Self::new("Panel")
+    }
+}
+
+impl Panel {
+    pub fn new(name: &str) -> Self {
+        let panel_helper = CosmicPanelConfig::cosmic_config(name).ok();
         let panel_config = panel_helper.as_ref().and_then(|config_helper| {
             let panel_config = CosmicPanelConfig::get_entry(config_helper).ok()?;
-            (panel_config.name == "Panel").then_some(panel_config)
+            (panel_config.name == name).then_some(panel_config)
         });
         let (cosmic_panel_config_helper, cosmic_panel_config) =
             match cosmic_config::Config::new("com.system76.CosmicPanel", 1) {
@@ -114,26 +184,75 @@ impl Default for Panel {
             .clone()
             .map(|config| config.size)
             .unwrap_or(cosmic_panel_config::PanelSize::M);
-        let show_panel = cosmic_panel_config.entries.iter().any(|e| e == "Panel");
-        let force_icons = cosmic_panel_button_config
+        let show_panel = cosmic_panel_config.entries.iter().any(|e| e == name);
+        let presentation = cosmic_panel_button_config
             .configs
-            .iter()
-            .find(|(e, _)| *e == "Panel")
-            .map(|(_, conf)| {
-                conf.force_presentation
-                    .as_ref()
-                    .is_some_and(|presentation| *presentation == Override::Icon)
-            })
-            .unwrap_or(false);
+            .get(name)
+            .and_then(|conf| conf.force_presentation.clone());
         let autohide = panel_config
             .clone()
             .map(|config| config.autohide.unwrap_or_default())
             .unwrap_or_default();
 
+        let mut outputs: Vec<CosmicPanelOuput> = [CosmicPanelOuput::All, CosmicPanelOuput::Active]
+            .into_iter()
+            .chain(outputs::connected().into_iter().map(CosmicPanelOuput::Name))
+            .collect();
+        if let Some(output) = panel_config.as_ref().map(|config| config.output.clone())
+            && !outputs.contains(&output)
+        {
+            outputs.push(output);
+        }
+
+        let labels = Labels {
+            anchors: vec![fl!("top"), fl!("bottom"), fl!("left"), fl!("right")],
+            layers: vec![
+                fl!("layer-background"),
+                fl!("layer-bottom"),
+                fl!("layer-top"),
+                fl!("layer-overlay"),
+            ],
+            interactivity: vec![
+                fl!("keyboard-none"),
+                fl!("keyboard-exclusive"),
+                fl!("keyboard-on-demand"),
+            ],
+            backgrounds: vec![fl!("match-desktop"), fl!("dark"), fl!("light")],
+            sizes: std::iter::once(fl!("same-as-panel"))
+                .chain(
+                    SECTION_SIZES
+                        .iter()
+                        .map(|s| size::name(s.clone()).to_string()),
+                )
+                .collect(),
+            presentations: vec![
+                fl!("presentation-default"),
+                fl!("presentation-icon"),
+                fl!("presentation-text"),
+            ],
+            outputs: outputs
+                .iter()
+                .map(|output| match output {
+                    CosmicPanelOuput::All => fl!("all-outputs"),
+                    CosmicPanelOuput::Active => fl!("active-output"),
+                    CosmicPanelOuput::Name(name) => name.clone(),
+                })
+                .collect(),
+        };
+
+        let title = match name {
+            "Panel" => fl!("panel"),
+            "Dock" => fl!("dock"),
+            _ => name.to_owned(),
+        };
+
         Self {
+            name: name.to_owned(),
+            title,
             panel_helper,
             panel_config,
             padding,
+            padding_range: 0..=20,
             margin,
             spacing,
             border_radius,
@@ -142,11 +261,20 @@ impl Default for Panel {
             cosmic_panel_config_helper,
             cosmic_panel_button_config,
             cosmic_panel_button_config_helper,
-            force_icons,
+            presentation,
             panel_size,
             autohide,
+            outputs,
+            labels,
+            highlight: None,
         }
     }
+
+    /// Sets the values offered by the padding slider.
+    pub fn padding_range(mut self, range: RangeInclusive<u32>) -> Self {
+        self.padding_range = range;
+        self
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -156,166 +284,530 @@ pub enum Message {
     SetSpacing(u32),
     SetBorder(u32),
     ShowPanel(bool),
-    ForceIcons(bool),
+    SetPresentation(usize),
     SetPanelSize(i32),
     SetWaitTime(u32),
     SetTransitionTime(u32),
     SetHandleSize(u32),
+    EnableAutohide(bool),
+    SetAnchor(usize),
+    SetAnchorGap(bool),
+    SetExpand(bool),
+    SetLayer(usize),
+    SetKeyboardInteractivity(usize),
+    SetOutput(usize),
+    SetBackground(usize),
+    SetOpacity(u32),
+    SetExclusiveZone(bool),
+    SetStartSize(usize),
+    SetCenterSize(usize),
+    SetEndSize(usize),
+}
+
+/// The dropdown index of a wing or center size, 0 meaning the panel size.
+fn section_size_index(size: Option<&PanelSize>) -> Option<usize> {
+    match size {
+        None => Some(0),
+        Some(size) => SECTION_SIZES
+            .iter()
+            .position(|s| s == size)
+            .map(|index| index + 1),
+    }
+}
+
+fn section_size(index: usize) -> Option<PanelSize> {
+    index
+        .checked_sub(1)
+        .and_then(|i| SECTION_SIZES.get(i).cloned())
 }
 
 impl Panel {
-    pub fn view<'a>(&self) -> Element<'a, Message> {
+    /// The settings of this page for the global search.
+    pub fn search_items(&self, page: Page) -> Vec<SearchItem> {
+        macro_rules! setting {
+            ($($key:literal),* $(,)?) => {
+                vec![$(SearchItem::new(page, fl!($key), Target::Setting($key))),*]
+            };
+        }
+
+        setting![
+            "position",
+            "output",
+            "extend",
+            "anchor-gap",
+            "exclusive-zone",
+            "layer",
+            "keyboard-interactivity",
+            "background",
+            "opacity",
+            "size-start",
+            "size-center",
+            "size-end",
+            "autohide",
+            "wait-time",
+            "transition-time",
+            "handle-size",
+            "show-panel",
+            "button-presentation",
+            "size",
+            "padding",
+            "margin",
+            "spacing",
+            "border-radius"
+        ]
+    }
+
+    /// Outlines a setting the global search jumped to, `None` clearing it.
+    pub fn set_highlight(&mut self, key: Option<&'static str>) {
+        self.highlight = key;
+    }
+
+    fn highlight<'a>(
+        &self,
+        key: &str,
+        item: impl Into<Element<'a, Message>>,
+    ) -> Element<'a, Message> {
+        if self.highlight == Some(key) {
+            widget::container(item)
+                .class(cosmic::theme::Container::custom(style::highlight))
+                .into()
+        } else {
+            item.into()
+        }
+    }
+
+    /// Takes in the config of this panel after it changed on disk.
+    pub fn sync_config(&mut self, config: CosmicPanelConfig) {
+        if config.name != self.name {
+            return;
+        }
+        self.padding = config.padding;
+        self.margin = config.margin;
+        self.spacing = config.spacing;
+        self.border_radius = config.border_radius;
+        self.panel_size = config.size.clone();
+        self.autohide = config.autohide.clone().unwrap_or_default();
+        if !self.outputs.contains(&config.output) {
+            self.labels.outputs.push(match &config.output {
+                CosmicPanelOuput::All => fl!("all-outputs"),
+                CosmicPanelOuput::Active => fl!("active-output"),
+                CosmicPanelOuput::Name(name) => name.clone(),
+            });
+            self.outputs.push(config.output.clone());
+        }
+        self.panel_config = Some(config);
+    }
+
+    /// Takes in the panel entries after they changed on disk.
+    pub fn sync_entries(&mut self, config: CosmicPanel) {
+        self.show_panel = config.entries.contains(&self.name);
+        self.cosmic_panel_config = config;
+    }
+
+    /// Takes in the panel button config after it changed on disk.
+    pub fn sync_buttons(&mut self, config: CosmicPanelButtonConfig) {
+        self.presentation = config
+            .configs
+            .get(&self.name)
+            .and_then(|conf| conf.force_presentation.clone());
+        self.cosmic_panel_button_config = config;
+    }
+
+    pub fn view<'a>(&'a self) -> Element<'a, Message> {
         let spacing = cosmic::theme::spacing();
+        let config = self.panel_config.as_ref();
+        let autohide_enabled = config.is_some_and(|config| config.autohide.is_some());
 
-        widget::scrollable(widget::settings::view_column(vec![
-            widget::settings::section()
-                .title("Panel")
+        let placement = widget::settings::section()
+            .title(fl!("placement"))
+            .add(
+                self.highlight(
+                    "position",
+                    widget::settings::item::builder(fl!("position"))
+                        .icon(icon!("resize-mode-symbolic", 18))
+                        .control(widget::dropdown(
+                            &self.labels.anchors,
+                            config.and_then(|config| {
+                                ANCHORS.iter().position(|a| *a == config.anchor)
+                            }),
+                            Message::SetAnchor,
+                        )),
+                ),
+            )
+            .add(
+                self.highlight(
+                    "output",
+                    widget::settings::item::builder(fl!("output"))
+                        .icon(icon!("view-coverflow-symbolic", 18))
+                        .control(widget::dropdown(
+                            &self.labels.outputs,
+                            config.and_then(|config| {
+                                self.outputs.iter().position(|o| *o == config.output)
+                            }),
+                            Message::SetOutput,
+                        )),
+                ),
+            )
+            .add(
+                self.highlight(
+                    "extend",
+                    widget::settings::item::builder(fl!("extend"))
+                        .icon(icon!("size-horizontally-symbolic", 18))
+                        .toggler(
+                            config.is_some_and(|config| config.expand_to_edges),
+                            Message::SetExpand,
+                        ),
+                ),
+            )
+            .add(
+                self.highlight(
+                    "anchor-gap",
+                    widget::settings::item::builder(fl!("anchor-gap"))
+                        .description(fl!("anchor-gap-description"))
+                        .icon(icon!("object-layout-symbolic", 18))
+                        .toggler(
+                            config.is_some_and(|config| config.anchor_gap),
+                            Message::SetAnchorGap,
+                        ),
+                ),
+            )
+            .add(
+                self.highlight(
+                    "exclusive-zone",
+                    widget::settings::item::builder(fl!("exclusive-zone"))
+                        .description(fl!("exclusive-zone-description"))
+                        .icon(icon!("selection-mode-symbolic", 18))
+                        .toggler(
+                            config.is_some_and(|config| config.exclusive_zone),
+                            Message::SetExclusiveZone,
+                        ),
+                ),
+            )
+            .add(
+                self.highlight(
+                    "layer",
+                    widget::settings::item::builder(fl!("layer"))
+                        .description(fl!("layer-description"))
+                        .icon(icon!("tabs-stack-symbolic", 18))
+                        .control(widget::dropdown(
+                            &self.labels.layers,
+                            config
+                                .and_then(|config| LAYERS.iter().position(|l| *l == config.layer)),
+                            Message::SetLayer,
+                        )),
+                ),
+            )
+            .add(
+                self.highlight(
+                    "keyboard-interactivity",
+                    widget::settings::item::builder(fl!("keyboard-interactivity"))
+                        .icon(icon!("keyboard-symbolic", 18))
+                        .control(widget::dropdown(
+                            &self.labels.interactivity,
+                            config.and_then(|config| {
+                                INTERACTIVITY
+                                    .iter()
+                                    .position(|k| *k == config.keyboard_interactivity)
+                            }),
+                            Message::SetKeyboardInteractivity,
+                        )),
+                ),
+            );
+
+        let opacity = config
+            .map(|config| (config.opacity * 100.0).round() as u32)
+            .unwrap_or(100);
+        let appearance = widget::settings::section()
+            .title(fl!("appearance"))
+            .add(
+                self.highlight(
+                    "background",
+                    widget::settings::item::builder(fl!("background"))
+                        .icon(icon!("dark-mode-symbolic", 18))
+                        .control(widget::dropdown(
+                            &self.labels.backgrounds,
+                            config.and_then(|config| {
+                                BACKGROUNDS.iter().position(|b| *b == config.background)
+                            }),
+                            Message::SetBackground,
+                        )),
+                ),
+            )
+            .add(
+                self.highlight(
+                    "opacity",
+                    widget::settings::item::builder(fl!("opacity"))
+                        .icon(icon!("dark-mode-2-symbolic", 18))
+                        .control(
+                            widget::row()
+                                .push(widget::slider(0..=100, opacity, Message::SetOpacity))
+                                .push(widget::text::text(format!("{opacity} %")))
+                                .spacing(spacing.space_xxs),
+                        ),
+                ),
+            )
+            .add(
+                self.highlight(
+                    "size-start",
+                    widget::settings::item::builder(fl!("size-start"))
+                        .icon(icon!("size-vertically-symbolic", 18))
+                        .control(widget::dropdown(
+                            &self.labels.sizes,
+                            section_size_index(
+                                config
+                                    .and_then(|config| config.size_wings.as_ref())
+                                    .and_then(|wings| wings.0.as_ref()),
+                            ),
+                            Message::SetStartSize,
+                        )),
+                ),
+            )
+            .add(
+                self.highlight(
+                    "size-center",
+                    widget::settings::item::builder(fl!("size-center"))
+                        .icon(icon!("size-vertically-symbolic", 18))
+                        .control(widget::dropdown(
+                            &self.labels.sizes,
+                            section_size_index(
+                                config.and_then(|config| config.size_center.as_ref()),
+                            ),
+                            Message::SetCenterSize,
+                        )),
+                ),
+            )
+            .add(
+                self.highlight(
+                    "size-end",
+                    widget::settings::item::builder(fl!("size-end"))
+                        .icon(icon!("size-vertically-symbolic", 18))
+                        .control(widget::dropdown(
+                            &self.labels.sizes,
+                            section_size_index(
+                                config
+                                    .and_then(|config| config.size_wings.as_ref())
+                                    .and_then(|wings| wings.1.as_ref()),
+                            ),
+                            Message::SetEndSize,
+                        )),
+                ),
+            );
+
+        let mut autohide = widget::settings::section().title(fl!("autohide")).add(
+            self.highlight(
+                "autohide",
+                widget::settings::item::builder(fl!("autohide"))
+                    .description(fl!("autohide-description"))
+                    .icon(icon!("eye-outline-symbolic", 18))
+                    .toggler(autohide_enabled, Message::EnableAutohide),
+            ),
+        );
+        if autohide_enabled {
+            autohide = autohide
                 .add(
-                    widget::settings::item::builder(fl!("show-panel"))
-                        .icon(icon!("eye-outline-symbolic", 18))
-                        .toggler(self.show_panel, Message::ShowPanel),
+                    self.highlight(
+                        "wait-time",
+                        widget::settings::item::builder(fl!("wait-time"))
+                            .description(fl!("wait-time-description"))
+                            .icon(icon!("size-vertically-symbolic", 18))
+                            .control(
+                                widget::row()
+                                    .push(
+                                        widget::slider(
+                                            0..=4000,
+                                            self.autohide.wait_time,
+                                            Message::SetWaitTime,
+                                        )
+                                        .breakpoints(&[1000, 2000, 3000])
+                                        .step(100u32),
+                                    )
+                                    .push(widget::text(format!("{} ms", self.autohide.wait_time)))
+                                    .spacing(spacing.space_xxs),
+                            ),
+                    ),
                 )
                 .add(
-                    widget::settings::item::builder(fl!("force-icon-buttons-in-panel"))
-                        .icon(icon!("smile-symbolic", 18))
-                        .toggler(self.force_icons, Message::ForceIcons),
+                    self.highlight(
+                        "transition-time",
+                        widget::settings::item::builder(fl!("transition-time"))
+                            .description(fl!("transition-time-description"))
+                            .icon(icon!("size-vertically-symbolic", 18))
+                            .control(
+                                widget::row()
+                                    .push(
+                                        widget::slider(
+                                            0..=4000,
+                                            self.autohide.transition_time,
+                                            Message::SetTransitionTime,
+                                        )
+                                        .breakpoints(&[1000, 2000, 3000])
+                                        .step(100u32),
+                                    )
+                                    .push(widget::text(format!(
+                                        "{} ms",
+                                        self.autohide.transition_time
+                                    )))
+                                    .spacing(spacing.space_xxs),
+                            ),
+                    ),
                 )
                 .add(
-                    widget::settings::item::builder(fl!("size"))
-                        .description(fl!("size-description"))
-                        .icon(icon!("size-vertically-symbolic", 18))
-                        .control(
-                            widget::row()
-                                .push(
-                                    widget::slider(
-                                        16..=112,
-                                        size::to_u32(self.panel_size.clone()) as i32,
-                                        Message::SetPanelSize,
+                    self.highlight(
+                        "handle-size",
+                        widget::settings::item::builder(fl!("handle-size"))
+                            .description(fl!("handle-size-description"))
+                            .icon(icon!("size-vertically-symbolic", 18))
+                            .control(
+                                widget::row()
+                                    .push(
+                                        widget::slider(
+                                            4..=32,
+                                            self.autohide.handle_size,
+                                            Message::SetHandleSize,
+                                        )
+                                        .breakpoints(&[8, 12, 16, 20, 24, 28])
+                                        .step(4u32),
                                     )
-                                    .step(4)
-                                    .breakpoints(&[32, 40, 56, 64, 96]),
-                                )
-                                .push(size::name(self.panel_size.clone()))
-                                .spacing(spacing.space_xxs),
-                        ),
-                )
+                                    .push(widget::text(format!("{} px", self.autohide.handle_size)))
+                                    .spacing(spacing.space_xxs),
+                            ),
+                    ),
+                );
+        }
+
+        widget::scrollable(widget::settings::view_column(vec![
+            widget::settings::section()
+                .title(self.title.clone())
                 .add(
-                    widget::settings::item::builder(fl!("padding"))
-                        .description(fl!("padding-description"))
-                        .icon(icon!("resize-mode-symbolic", 18))
-                        .control(
-                            widget::row()
-                                .push(widget::slider(0..=20, self.padding, Message::SetPadding))
-                                .push(widget::text::text(format!("{} px", self.padding)))
-                                .spacing(spacing.space_xxs),
-                        ),
+                    self.highlight(
+                        "show-panel",
+                        widget::settings::item::builder(fl!("show-panel"))
+                            .icon(icon!("eye-outline-symbolic", 18))
+                            .toggler(self.show_panel, Message::ShowPanel),
+                    ),
                 )
                 .add(
-                    widget::settings::item::builder(fl!("margin"))
-                        .description(fl!("margin-description"))
-                        .icon(icon!("object-layout-symbolic", 18))
-                        .control(
-                            widget::row()
-                                .push(widget::slider(0..=20, self.margin, Message::SetMargin))
-                                .push(widget::text::text(format!("{} px", self.margin)))
-                                .spacing(spacing.space_xxs),
-                        ),
+                    self.highlight(
+                        "button-presentation",
+                        widget::settings::item::builder(fl!("button-presentation"))
+                            .description(fl!("button-presentation-description"))
+                            .icon(icon!("smile-symbolic", 18))
+                            .control(widget::dropdown(
+                                &self.labels.presentations,
+                                Some(match &self.presentation {
+                                    None => 0,
+                                    Some(presentation) => PRESENTATIONS
+                                        .iter()
+                                        .position(|p| p == presentation)
+                                        .map_or(0, |index| index + 1),
+                                }),
+                                Message::SetPresentation,
+                            )),
+                    ),
                 )
                 .add(
-                    widget::settings::item::builder(fl!("spacing"))
-                        .description(fl!("spacing-description"))
-                        .icon(icon!("size-horizontally-symbolic", 18))
-                        .control(
-                            widget::row()
-                                .push(widget::slider(0..=28, self.spacing, Message::SetSpacing))
-                                .push(widget::text::text(format!("{} px", self.spacing)))
-                                .spacing(spacing.space_xxs),
-                        ),
+                    self.highlight(
+                        "size",
+                        widget::settings::item::builder(fl!("size"))
+                            .description(fl!("size-description"))
+                            .icon(icon!("size-vertically-symbolic", 18))
+                            .control(
+                                widget::row()
+                                    .push(
+                                        widget::slider(
+                                            16..=112,
+                                            size::to_u32(self.panel_size.clone()) as i32,
+                                            Message::SetPanelSize,
+                                        )
+                                        .step(4)
+                                        .breakpoints(&[32, 40, 56, 64, 96]),
+                                    )
+                                    .push(size::name(self.panel_size.clone()))
+                                    .spacing(spacing.space_xxs),
+                            ),
+                    ),
                 )
                 .add(
-                    widget::settings::item::builder(fl!("border-radius"))
-                        .description(fl!("border-radius-description"))
-                        .icon(icon!("size-horizontally-symbolic", 18))
-                        .control(
-                            widget::row()
-                                .push(widget::slider(
-                                    0..=160,
-                                    self.border_radius,
-                                    Message::SetBorder,
-                                ))
-                                .push(widget::text::text(format!("{} px", self.border_radius)))
-                                .spacing(spacing.space_xxs),
-                        ),
+                    self.highlight(
+                        "padding",
+                        widget::settings::item::builder(fl!("padding"))
+                            .description(fl!("padding-description"))
+                            .icon(icon!("resize-mode-symbolic", 18))
+                            .control(
+                                widget::row()
+                                    .push(widget::slider(
+                                        self.padding_range.clone(),
+                                        self.padding,
+                                        Message::SetPadding,
+                                    ))
+                                    .push(widget::text::text(format!("{} px", self.padding)))
+                                    .spacing(spacing.space_xxs),
+                            ),
+                    ),
                 )
-                .into(),
-            widget::settings::section()
-                .title(fl!("animation-speed"))
                 .add(
-                    widget::settings::item::builder(fl!("wait-time"))
-                        .description(fl!("wait-time-description"))
-                        .icon(icon!("size-vertically-symbolic", 18))
-                        .control(
-                            widget::row()
-                                .push(
-                                    widget::slider(
-                                        0..=4000,
-                                        self.autohide.wait_time,
-                                        Message::SetWaitTime,
-                                    )
-                                    .breakpoints(&[1000, 2000, 3000])
-                                    .step(100u32),
-                                )
-                                .push(widget::text(format!("{} ms", self.autohide.wait_time)))
-                                .spacing(spacing.space_xxs),
-                        ),
+                    self.highlight(
+                        "margin",
+                        widget::settings::item::builder(fl!("margin"))
+                            .description(fl!("margin-description"))
+                            .icon(icon!("object-layout-symbolic", 18))
+                            .control(
+                                widget::row()
+                                    .push(widget::slider(0..=20, self.margin, Message::SetMargin))
+                                    .push(widget::text::text(format!("{} px", self.margin)))
+                                    .spacing(spacing.space_xxs),
+                            ),
+                    ),
                 )
                 .add(
-                    widget::settings::item::builder(fl!("transition-time"))
-                        .description(fl!("transition-time-description"))
-                        .icon(icon!("size-vertically-symbolic", 18))
-                        .control(
-                            widget::row()
-                                .push(
-                                    widget::slider(
-                                        0..=4000,
-                                        self.autohide.transition_time,
-                                        Message::SetTransitionTime,
-                                    )
-                                    .breakpoints(&[1000, 2000, 3000])
-                                    .step(100u32),
-                                )
-                                .push(widget::text(format!(
-                                    "{} ms",
-                                    self.autohide.transition_time
-                                )))
-                                .spacing(spacing.space_xxs),
-                        ),
+                    self.highlight(
+                        "spacing",
+                        widget::settings::item::builder(fl!("spacing"))
+                            .description(fl!("spacing-description"))
+                            .icon(icon!("size-horizontally-symbolic", 18))
+                            .control(
+                                widget::row()
+                                    .push(widget::slider(0..=28, self.spacing, Message::SetSpacing))
+                                    .push(widget::text::text(format!("{} px", self.spacing)))
+                                    .spacing(spacing.space_xxs),
+                            ),
+                    ),
                 )
                 .add(
-                    widget::settings::item::builder(fl!("handle-size"))
-                        .description(fl!("handle-size-description"))
-                        .icon(icon!("size-vertically-symbolic", 18))
-                        .control(
-                            widget::row()
-                                .push(
-                                    widget::slider(
-                                        4..=32,
-                                        self.autohide.handle_size,
-                                        Message::SetHandleSize,
-                                    )
-                                    .breakpoints(&[8, 12, 16, 20, 24, 28])
-                                    .step(4u32),
-                                )
-                                .push(widget::text(format!("{} px", self.autohide.handle_size)))
-                                .spacing(spacing.space_xxs),
-                        ),
+                    self.highlight(
+                        "border-radius",
+                        widget::settings::item::builder(fl!("border-radius"))
+                            .description(fl!("border-radius-description"))
+                            .icon(icon!("size-horizontally-symbolic", 18))
+                            .control(
+                                widget::row()
+                                    .push(widget::slider(
+                                        0..=160,
+                                        self.border_radius,
+                                        Message::SetBorder,
+                                    ))
+                                    .push(widget::text::text(format!("{} px", self.border_radius)))
+                                    .spacing(spacing.space_xxs),
+                            ),
+                    ),
                 )
                 .into(),
+            placement.into(),
+            appearance.into(),
+            autohide.into(),
         ]))
         .into()
     }
 
-    pub fn update(&mut self, message: Message) -> Task<crate::app::message::Message> {
+    /// Writes a setting of the panel.
+    ///
+    /// `page` wraps the message for the page showing this panel, so a failed
+    /// write can be retried from the error.
+    pub fn update(
+        &mut self,
+        message: Message,
+        page: fn(Message) -> crate::app::message::Message,
+    ) -> Task<crate::app::message::Message> {
         let Some(panel_helper) = &self.panel_helper else {
             return cosmic::Task::none();
         };
@@ -323,55 +815,53 @@ impl Panel {
             return cosmic::Task::none();
         };
 
+        let retry = page(message.clone());
+        let mut failure = None;
         match message {
             Message::SetPadding(padding) => {
                 self.padding = padding;
                 let update = panel_config.set_padding(panel_helper, self.padding);
                 if let Err(err) = update {
-                    log::error!("Error updating panel padding: {}", err);
+                    failure = Some(err);
                 }
             }
             Message::SetMargin(margin) => {
                 self.margin = margin;
                 let update = panel_config.set_margin(panel_helper, self.margin);
                 if let Err(err) = update {
-                    log::error!("Error updating panel margin: {}", err);
+                    failure = Some(err);
                 }
             }
             Message::SetSpacing(spacing) => {
                 self.spacing = spacing;
                 let update = panel_config.set_spacing(panel_helper, self.spacing);
                 if let Err(err) = update {
-                    log::error!("Error updating panel spacing: {}", err);
+                    failure = Some(err);
                 }
             }
             Message::SetBorder(border_radius) => {
                 self.border_radius = border_radius;
                 let update = panel_config.set_border_radius(panel_helper, self.border_radius);
                 if let Err(err) = update {
-                    log::error!("Error updating panel border: {}", err);
+                    failure = Some(err);
                 }
             }
-            Message::ForceIcons(force) => {
+            Message::SetPresentation(index) => {
+                let presentation = index
+                    .checked_sub(1)
+                    .and_then(|index| PRESENTATIONS.get(index).cloned());
                 let mut configs = self.cosmic_panel_button_config.configs.clone();
-                if let Some(inner_config) = configs.get_mut("Panel") {
-                    inner_config.force_presentation =
-                        if force { Some(Override::Icon) } else { None };
-                } else {
-                    configs.insert(
-                        "Panel".to_owned(),
-                        IndividualConfig {
-                            force_presentation: if force { Some(Override::Icon) } else { None },
-                        },
-                    );
-                }
+                configs
+                    .entry(self.name.clone())
+                    .or_insert_with(IndividualConfig::default)
+                    .force_presentation = presentation.clone();
 
                 if let Some(helper) = &self.cosmic_panel_button_config_helper {
                     let update = self.cosmic_panel_button_config.set_configs(helper, configs);
                     if let Err(err) = update {
-                        log::error!("Error updating cosmic panel button configs: {}", err);
+                        failure = Some(err);
                     } else {
-                        self.force_icons = force;
+                        self.presentation = presentation;
                     }
                 }
             }
@@ -381,14 +871,14 @@ impl Panel {
                         .cosmic_panel_config
                         .entries
                         .iter()
-                        .any(|e| e == "Panel")
+                        .any(|e| *e == self.name)
                     {
                         let mut entries = self.cosmic_panel_config.entries.clone();
-                        entries.push("Panel".to_owned());
+                        entries.push(self.name.clone());
                         if let Some(helper) = &self.cosmic_panel_config_helper {
                             let update = self.cosmic_panel_config.set_entries(helper, entries);
                             if let Err(err) = update {
-                                log::error!("Error updating cosmic panel entries: {}", err);
+                                failure = Some(err);
                             } else {
                                 self.show_panel = true;
                             }
@@ -398,14 +888,14 @@ impl Panel {
                     .cosmic_panel_config
                     .entries
                     .iter()
-                    .position(|e| e == "Panel")
+                    .position(|e| *e == self.name)
                 {
                     let mut entries = self.cosmic_panel_config.entries.clone();
                     entries.remove(i);
                     if let Some(helper) = &self.cosmic_panel_config_helper {
                         let update = self.cosmic_panel_config.set_entries(helper, entries);
                         if let Err(err) = update {
-                            log::error!("Error updating cosmic panel entries: {}", err);
+                            failure = Some(err);
                         } else {
                             self.show_panel = false;
                         }
@@ -416,7 +906,7 @@ impl Panel {
                 self.panel_size = cosmic_panel_config::PanelSize::Custom(panel_size as u32);
                 let update = panel_config.set_size(panel_helper, self.panel_size.clone());
                 if let Err(err) = update {
-                    log::error!("Error updating panel spacing: {}", err);
+                    failure = Some(err);
                 }
             }
             Message::SetWaitTime(wait_time) => {
@@ -424,7 +914,7 @@ impl Panel {
                 if let Err(err) =
                     panel_config.set_autohide(panel_helper, Some(self.autohide.clone()))
                 {
-                    log::error!("Error updating panel wait time: {}", err);
+                    failure = Some(err);
                 }
             }
             Message::SetTransitionTime(transition_time) => {
@@ -432,7 +922,7 @@ impl Panel {
                 if let Err(err) =
                     panel_config.set_autohide(panel_helper, Some(self.autohide.clone()))
                 {
-                    log::error!("Error updating panel transition time: {}", err);
+                    failure = Some(err);
                 }
             }
             Message::SetHandleSize(handle_size) => {
@@ -440,10 +930,103 @@ impl Panel {
                 if let Err(err) =
                     panel_config.set_autohide(panel_helper, Some(self.autohide.clone()))
                 {
-                    log::error!("Error updating panel handle size: {}", err);
+                    failure = Some(err);
+                }
+            }
+            Message::EnableAutohide(enable) => {
+                let autohide = enable.then(|| self.autohide.clone());
+                if let Err(err) = panel_config.set_autohide(panel_helper, autohide) {
+                    failure = Some(err);
+                }
+            }
+            Message::SetAnchor(index) => {
+                if let Some(anchor) = ANCHORS.get(index)
+                    && let Err(err) = panel_config.set_anchor(panel_helper, *anchor)
+                {
+                    failure = Some(err);
+                }
+            }
+            Message::SetAnchorGap(anchor_gap) => {
+                if let Err(err) = panel_config.set_anchor_gap(panel_helper, anchor_gap) {
+                    failure = Some(err);
+                }
+            }
+            Message::SetExpand(expand) => {
+                if let Err(err) = panel_config.set_expand_to_edges(panel_helper, expand) {
+                    failure = Some(err);
+                }
+            }
+            Message::SetLayer(index) => {
+                if let Some(layer) = LAYERS.get(index).cloned()
+                    && let Err(err) = panel_config.set_layer(panel_helper, layer)
+                {
+                    failure = Some(err);
                 }
             }
+            Message::SetKeyboardInteractivity(index) => {
+                if let Some(interactivity) = INTERACTIVITY.get(index).cloned()
+                    && let Err(err) =
+                        panel_config.set_keyboard_interactivity(panel_helper, interactivity)
+                {
+                    failure = Some(err);
+                }
+            }
+            Message::SetOutput(index) => {
+                if let Some(output) = self.outputs.get(index).cloned()
+                    && let Err(err) = panel_config.set_output(panel_helper, output)
+                {
+                    failure = Some(err);
+                }
+            }
+            Message::SetBackground(index) => {
+                if let Some(background) = BACKGROUNDS.get(index).cloned()
+                    && let Err(err) = panel_config.set_background(panel_helper, background)
+                {
+                    failure = Some(err);
+                }
+            }
+            Message::SetOpacity(opacity) => {
+                if let Err(err) = panel_config.set_opacity(panel_helper, opacity as f32 / 100.0) {
+                    failure = Some(err);
+                }
+            }
+            Message::SetExclusiveZone(exclusive_zone) => {
+                if let Err(err) = panel_config.set_exclusive_zone(panel_helper, exclusive_zone) {
+                    failure = Some(err);
+                }
+            }
+            Message::SetStartSize(index) => {
+                let end = panel_config
+                    .size_wings
+                    .as_ref()
+                    .and_then(|wings| wings.1.clone());
+                let wings = Some((section_size(index), end)).filter(|w| *w != (None, None));
+                if let Err(err) = panel_config.set_size_wings(panel_helper, wings) {
+                    failure = Some(err);
+                }
+            }
+            Message::SetEndSize(index) => {
+                let start = panel_config
+                    .size_wings
+                    .as_ref()
+                    .and_then(|wings| wings.0.clone());
+                let wings = Some((start, section_size(index))).filter(|w| *w != (None, None));
+                if let Err(err) = panel_config.set_size_wings(panel_helper, wings) {
+                    failure = Some(err);
+                }
+            }
+            Message::SetCenterSize(index) => {
+                if let Err(err) = panel_config.set_size_center(panel_helper, section_size(index)) {
+                    failure = Some(err);
+                }
+            }
+        }
+
+        match failure {
+            Some(err) => Report::new(fl!("failed-save-panel", name = self.name.as_str()), err)
+                .retry(retry)
+                .task(),
+            None => Task::none(),
         }
-        Task::none()
     }
 }
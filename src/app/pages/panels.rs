@@ -0,0 +1,339 @@
+use cosmic::{
+    Element, Task,
+    cosmic_config::{self, Config, CosmicConfigEntry},
+    iced::Alignment,
+    widget,
+};
+use cosmic_panel_config::{CosmicPanelConfig, PanelAnchor};
+
+use super::panel::{self, CosmicPanel, Panel, config::CosmicPanelButtonConfig};
+use crate::app::{
+    page::Page,
+    report::Report,
+    search::{SearchItem, Target},
+};
+use crate::{Error, fl, icon};
+
+/// Lists every bar in `CosmicPanel::entries` and edits any of them.
+pub struct Panels {
+    helper: Option<Config>,
+    config: CosmicPanel,
+    editing: Option<Panel>,
+    new_name: String,
+    new_anchor: usize,
+    anchors: Vec<String>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Edit(String),
+    Back,
+    // intercepted in the outer update fn to confirm
+    Delete(String),
+    DeleteConfirmed(String),
+    SetNewName(String),
+    SetNewAnchor(usize),
+    Create,
+    Panel(panel::Message),
+}
+
+/// The bars COSMIC ships with, which can be edited but not deleted.
+const BUILT_IN: [&str; 2] = ["Panel", "Dock"];
+
+const ANCHORS: [PanelAnchor; 4] = [
+    PanelAnchor::Top,
+    PanelAnchor::Bottom,
+    PanelAnchor::Left,
+    PanelAnchor::Right,
+];
+
+impl Default for Panels {
+    fn default() -> Self {
+        let (helper, config) = match cosmic_config::Config::new("com.system76.CosmicPanel", 1) {
+            Ok(helper) => {
+                let config = match CosmicPanel::get_entry(&helper) {
+                    Ok(ok) => ok,
+                    Err((errs, config)) => {
+                        log::error!("errors loading config: {:?}", errs);
+                        config
+                    }
+                };
+                (Some(helper), config)
+            }
+            Err(err) => {
+                log::error!("failed to create config handler: {}", err);
+                (None, CosmicPanel::default())
+            }
+        };
+
+        Self {
+            helper,
+            config,
+            editing: None,
+            new_name: String::new(),
+            new_anchor: 0,
+            anchors: vec![fl!("top"), fl!("bottom"), fl!("left"), fl!("right")],
+            error: None,
+        }
+    }
+}
+
+impl Panels {
+    pub fn view<'a>(&'a self) -> Element<'a, Message> {
+        let spacing = cosmic::theme::spacing();
+
+        if let Some(panel) = &self.editing {
+            return widget::column()
+                .push(
+                    widget::button::text(fl!("panels"))
+                        .leading_icon(widget::icon::from_name("go-previous-symbolic"))
+                        .on_press(Message::Back),
+                )
+                .push(panel.view().map(Message::Panel))
+                .spacing(spacing.space_xs)
+                .into();
+        }
+
+        let entries = self.config.entries.iter().fold(
+            widget::settings::section().title(fl!("panels")),
+            |section, name| {
+                section.add(
+                    widget::settings::item::builder(name.clone())
+                        .icon(icon!("dock-top-symbolic", 18))
+                        .control(
+                            widget::row()
+                                .push(
+                                    widget::button::icon(widget::icon::from_name("edit-symbolic"))
+                                        .on_press(Message::Edit(name.clone())),
+                                )
+                                .push(
+                                    widget::button::icon(widget::icon::from_name(
+                                        "user-trash-symbolic",
+                                    ))
+                                    .class(cosmic::style::Button::Destructive)
+                                    .on_press_maybe(
+                                        (!BUILT_IN.contains(&name.as_str()))
+                                            .then(|| Message::Delete(name.clone())),
+                                    ),
+                                )
+                                .spacing(spacing.space_xxs),
+                        ),
+                )
+            },
+        );
+
+        let create = widget::settings::section()
+            .title(fl!("add-panel"))
+            .add(
+                widget::settings::item::builder(fl!("panel-name"))
+                    .description(self.error.clone().unwrap_or_default())
+                    .control(
+                        widget::text_input(fl!("panel-name"), &self.new_name)
+                            .on_input(Message::SetNewName)
+                            .on_submit(|_| Message::Create),
+                    ),
+            )
+            .add(
+                widget::settings::item::builder(fl!("position"))
+                    .icon(icon!("resize-mode-symbolic", 18))
+                    .control(widget::dropdown(
+                        &self.anchors,
+                        Some(self.new_anchor),
+                        Message::SetNewAnchor,
+                    )),
+            )
+            .add(
+                widget::row()
+                    .push(widget::horizontal_space())
+                    .push(widget::button::suggested(fl!("add")).on_press(Message::Create))
+                    .align_y(Alignment::Center),
+            );
+
+        widget::scrollable(widget::settings::view_column(vec![
+            entries.into(),
+            create.into(),
+        ]))
+        .into()
+    }
+
+    pub fn update(&mut self, message: Message) -> Task<crate::app::message::Message> {
+        match message {
+            Message::Edit(name) => self.editing = Some(Panel::new(&name)),
+            Message::Back => {
+                self.editing = None;
+                self.reload();
+            }
+            Message::Delete(_) => {}
+            Message::DeleteConfirmed(name) => {
+                // The name ends up in a path handed to `remove_dir_all`.
+                if !is_safe_name(&name) || BUILT_IN.contains(&name.as_str()) {
+                    log::warn!("Refusing to delete panel {name:?}");
+                    return Task::none();
+                }
+                let entries = self
+                    .config
+                    .entries
+                    .iter()
+                    .filter(|entry| **entry != name)
+                    .cloned()
+                    .collect();
+                // The config would otherwise come back with a new panel of the same name.
+                let removed = self
+                    .set_entries(entries)
+                    .and_then(|_| match config_dir(&name) {
+                        Some(dir) if dir.exists() => Ok(std::fs::remove_dir_all(&dir)?),
+                        _ => Ok(()),
+                    });
+                if let Err(err) = removed {
+                    return Report::new(fl!("failed-delete-panel", name = name.as_str()), err)
+                        .retry(crate::app::message::Message::Panels(
+                            Message::DeleteConfirmed(name),
+                        ))
+                        .task();
+                }
+            }
+            Message::SetNewName(name) => {
+                self.new_name = name;
+                self.error = None;
+            }
+            Message::SetNewAnchor(index) => self.new_anchor = index,
+            Message::Create => match self.new_panel_name() {
+                Ok(name) => {
+                    if let Err(err) = self.create(&name) {
+                        return Report::new(fl!("failed-create-panel", name = name.as_str()), err)
+                            .retry(crate::app::message::Message::Panels(Message::Create))
+                            .task();
+                    }
+                    self.new_name.clear();
+                    self.editing = Some(Panel::new(&name));
+                }
+                Err(error) => self.error = Some(error),
+            },
+            Message::Panel(message) => {
+                if let Some(panel) = &mut self.editing {
+                    return panel.update(message, |message| {
+                        crate::app::message::Message::Panels(Message::Panel(message))
+                    });
+                }
+            }
+        }
+        Task::none()
+    }
+
+    /// The names of the bars in `CosmicPanel::entries`.
+    pub fn entries(&self) -> &[String] {
+        &self.config.entries
+    }
+
+    /// Each bar for the global search, opening its editor, followed by the
+    /// settings of the bar being edited.
+    pub fn search_items(&self) -> Vec<SearchItem> {
+        let mut items: Vec<SearchItem> = self
+            .config
+            .entries
+            .iter()
+            .map(|name| SearchItem::new(Page::Panels, name.clone(), Target::Panel(name.clone())))
+            .collect();
+        if let Some(panel) = &self.editing {
+            items.extend(panel.search_items(Page::Panels));
+        }
+        items
+    }
+
+    /// Outlines a setting of the bar being edited.
+    pub fn set_highlight(&mut self, key: Option<&'static str>) {
+        if let Some(panel) = &mut self.editing {
+            panel.set_highlight(key);
+        }
+    }
+
+    /// Takes in the panel entries after they changed on disk.
+    pub fn sync_entries(&mut self, config: CosmicPanel) {
+        if let Some(panel) = &mut self.editing {
+            panel.sync_entries(config.clone());
+        }
+        self.config = config;
+    }
+
+    /// Takes in the config of a bar after it changed on disk.
+    pub fn sync_config(&mut self, config: CosmicPanelConfig) {
+        if let Some(panel) = &mut self.editing {
+            panel.sync_config(config);
+        }
+    }
+
+    /// Takes in the panel button config after it changed on disk.
+    pub fn sync_buttons(&mut self, config: CosmicPanelButtonConfig) {
+        if let Some(panel) = &mut self.editing {
+            panel.sync_buttons(config);
+        }
+    }
+
+    fn reload(&mut self) {
+        if let Some(helper) = &self.helper {
+            self.config = CosmicPanel::get_entry(helper).unwrap_or_else(|(errs, config)| {
+                log::error!("errors loading config: {:?}", errs);
+                config
+            });
+        }
+    }
+
+    fn set_entries(&mut self, entries: Vec<String>) -> Result<(), Error> {
+        if let Some(helper) = &self.helper {
+            self.config.set_entries(helper, entries)?;
+        }
+        Ok(())
+    }
+
+    /// The name typed for a new bar, or why it can't be used.
+    fn new_panel_name(&self) -> Result<String, String> {
+        let name = self.new_name.trim().to_owned();
+        if name.is_empty() {
+            return Err(fl!("panel-name-empty"));
+        }
+        if !is_safe_name(&name) {
+            return Err(fl!("panel-name-invalid"));
+        }
+        // A leftover config dir would hand its settings to the new bar.
+        if self
+            .config
+            .entries
+            .iter()
+            .any(|entry| entry.eq_ignore_ascii_case(&name))
+            || config_dir(&name).is_some_and(|dir| dir.exists())
+        {
+            return Err(fl!("panel-name-taken"));
+        }
+        Ok(name)
+    }
+
+    /// Writes the config of a new bar and adds it to the panel entries.
+    fn create(&mut self, name: &str) -> Result<(), Error> {
+        let config = CosmicPanelConfig {
+            name: name.to_owned(),
+            anchor: ANCHORS[self.new_anchor.min(ANCHORS.len() - 1)],
+            ..Default::default()
+        };
+        CosmicPanelConfig::cosmic_config(name).and_then(|helper| config.write_entry(&helper))?;
+
+        let mut entries = self.config.entries.clone();
+        entries.push(name.to_owned());
+        self.set_entries(entries)
+    }
+}
+
+/// Whether a bar name can be used as a config directory name.
+///
+/// The name becomes a config directory, and entries are told apart by it.
+fn is_safe_name(name: &str) -> bool {
+    !name.is_empty() && !name.starts_with('.') && !name.chars().any(|c| c == '/' || c.is_control())
+}
+
+/// Where cosmic-config keeps the config of a bar.
+fn config_dir(name: &str) -> Option<std::path::PathBuf> {
+    dirs::config_dir().map(|dir| {
+        dir.join("cosmic")
+            .join(format!("com.system76.CosmicPanel.{name}"))
+    })
+}
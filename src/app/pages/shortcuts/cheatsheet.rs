@@ -0,0 +1,293 @@
+use std::collections::BTreeMap;
+
+use cosmic_settings_config::{Shortcuts, shortcuts::Action};
+
+use super::action_name;
+use crate::fl;
+
+/// The groups actions are listed under in the cheat sheet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Category {
+    Windows,
+    Tiling,
+    Workspaces,
+    Outputs,
+    Applications,
+    System,
+}
+
+impl Category {
+    pub fn name(self) -> String {
+        match self {
+            Category::Windows => fl!("category-windows"),
+            Category::Tiling => fl!("category-tiling"),
+            Category::Workspaces => fl!("category-workspaces"),
+            Category::Outputs => fl!("category-outputs"),
+            Category::Applications => fl!("category-applications"),
+            Category::System => fl!("category-system"),
+        }
+    }
+
+    /// The category of an action, `None` for actions that disable a binding.
+    ///
+    /// Anything not listed is about the focused window.
+    fn of(action: &Action) -> Option<Category> {
+        let category = match action {
+            Action::Disable => return None,
+            Action::Spawn(_) => Category::Applications,
+            Action::System(_) | Action::Terminate | Action::ZoomIn | Action::ZoomOut => {
+                Category::System
+            }
+            Action::Workspace(_)
+            | Action::LastWorkspace
+            | Action::NextWorkspace
+            | Action::PreviousWorkspace
+            | Action::MoveToWorkspace(_)
+            | Action::MoveToLastWorkspace
+            | Action::MoveToNextWorkspace
+            | Action::MoveToPreviousWorkspace
+            | Action::SendToWorkspace(_)
+            | Action::SendToLastWorkspace
+            | Action::SendToNextWorkspace
+            | Action::SendToPreviousWorkspace => Category::Workspaces,
+            Action::SwitchOutput(_)
+            | Action::NextOutput
+            | Action::PreviousOutput
+            | Action::MoveToOutput(_)
+            | Action::MoveToNextOutput
+            | Action::MoveToPreviousOutput
+            | Action::SendToOutput(_)
+            | Action::SendToNextOutput
+            | Action::SendToPreviousOutput
+            | Action::MigrateWorkspaceToOutput(_)
+            | Action::MigrateWorkspaceToNextOutput
+            | Action::MigrateWorkspaceToPreviousOutput => Category::Outputs,
+            Action::Orientation(_)
+            | Action::ToggleOrientation
+            | Action::ToggleStacking
+            | Action::ToggleTiling => Category::Tiling,
+            _ => Category::Windows,
+        };
+        Some(category)
+    }
+}
+
+/// A format the cheat sheet can be exported to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Html,
+    Markdown,
+    Svg,
+}
+
+impl Format {
+    pub const ALL: [Format; 3] = [Format::Html, Format::Markdown, Format::Svg];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Format::Html => "HTML",
+            Format::Markdown => "Markdown",
+            Format::Svg => "SVG",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Html => "html",
+            Format::Markdown => "md",
+            Format::Svg => "svg",
+        }
+    }
+}
+
+/// The shortcuts in effect, the system ones with the custom ones on top,
+/// grouped by what they do.
+#[derive(Debug, Clone, Default)]
+pub struct CheatSheet {
+    pub categories: Vec<(Category, Vec<(String, String)>)>,
+}
+
+impl CheatSheet {
+    pub fn new(custom: &Shortcuts, system: &Shortcuts) -> Self {
+        let mut effective = system.0.clone();
+        effective.extend(custom.0.clone());
+
+        let mut categories: BTreeMap<Category, Vec<(String, String)>> = BTreeMap::new();
+        for (binding, action) in effective {
+            if let Some(category) = Category::of(&action) {
+                categories
+                    .entry(category)
+                    .or_default()
+                    .push((binding.to_string(), action_name(&action)));
+            }
+        }
+        for shortcuts in categories.values_mut() {
+            shortcuts.sort_by(|(a, _), (b, _)| a.cmp(b));
+        }
+
+        Self {
+            categories: categories.into_iter().collect(),
+        }
+    }
+
+    pub fn render(&self, format: Format) -> String {
+        match format {
+            Format::Html => self.html(),
+            Format::Markdown => self.markdown(),
+            Format::Svg => self.svg(),
+        }
+    }
+
+    fn markdown(&self) -> String {
+        let mut out = format!("# {}\n", fl!("shortcut-cheat-sheet"));
+        for (category, shortcuts) in &self.categories {
+            out.push_str(&format!(
+                "\n## {}\n\n| {} | {} |\n| --- | --- |\n",
+                category.name(),
+                fl!("binding"),
+                fl!("action")
+            ));
+            for (binding, action) in shortcuts {
+                out.push_str(&format!(
+                    "| `{}` | {} |\n",
+                    binding.replace('|', "\\|"),
+                    action.replace('|', "\\|")
+                ));
+            }
+        }
+        out
+    }
+
+    fn html(&self) -> String {
+        let title = escape(&fl!("shortcut-cheat-sheet"));
+        let mut out = format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n\
+             <style>\n\
+             body {{ font-family: sans-serif; margin: 2em; columns: 2; }}\n\
+             section {{ break-inside: avoid; margin-bottom: 1.5em; }}\n\
+             table {{ border-collapse: collapse; width: 100%; }}\n\
+             td {{ padding: 0.2em 0.5em; border-bottom: 1px solid #ddd; }}\n\
+             kbd {{ font-family: monospace; background: #eee; border-radius: 3px; padding: 0 0.3em; }}\n\
+             h1 {{ column-span: all; }}\n\
+             </style>\n</head>\n<body>\n<h1>{title}</h1>\n"
+        );
+        for (category, shortcuts) in &self.categories {
+            out.push_str(&format!(
+                "<section>\n<h2>{}</h2>\n<table>\n",
+                escape(&category.name())
+            ));
+            for (binding, action) in shortcuts {
+                out.push_str(&format!(
+                    "<tr><td><kbd>{}</kbd></td><td>{}</td></tr>\n",
+                    escape(binding),
+                    escape(action)
+                ));
+            }
+            out.push_str("</table>\n</section>\n");
+        }
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+
+    fn svg(&self) -> String {
+        const WIDTH: u32 = 720;
+        const TITLE: u32 = 48;
+        const HEADING: u32 = 36;
+        const ROW: u32 = 22;
+
+        let rows: u32 = self
+            .categories
+            .iter()
+            .map(|(_, shortcuts)| HEADING + ROW * shortcuts.len() as u32)
+            .sum();
+        let height = TITLE + rows + 24;
+
+        let mut out = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{WIDTH}\" height=\"{height}\" \
+             font-family=\"sans-serif\" font-size=\"13\">\n\
+             <rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n\
+             <text x=\"24\" y=\"36\" font-size=\"22\" font-weight=\"bold\">{}</text>\n",
+            escape(&fl!("shortcut-cheat-sheet"))
+        );
+        let mut y = TITLE;
+        for (category, shortcuts) in &self.categories {
+            y += HEADING;
+            out.push_str(&format!(
+                "<text x=\"24\" y=\"{}\" font-size=\"16\" font-weight=\"bold\">{}</text>\n",
+                y - 10,
+                escape(&category.name())
+            ));
+            for (binding, action) in shortcuts {
+                y += ROW;
+                out.push_str(&format!(
+                    "<text x=\"24\" y=\"{}\" font-family=\"monospace\">{}</text>\
+                     <text x=\"280\" y=\"{}\">{}</text>\n",
+                    y - 6,
+                    escape(binding),
+                    y - 6,
+                    escape(action)
+                ));
+            }
+        }
+        out.push_str("</svg>\n");
+        out
+    }
+}
+
+/// Escapes text for HTML and SVG.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sheet() -> CheatSheet {
+        CheatSheet {
+            categories: vec![(
+                Category::Applications,
+                vec![("Super+|".into(), "echo \"<a>\" | tee & wait".into())],
+            )],
+        }
+    }
+
+    #[test]
+    fn categorizes_actions() {
+        assert_eq!(Category::of(&Action::Disable), None);
+        assert_eq!(
+            Category::of(&Action::Workspace(2)),
+            Some(Category::Workspaces)
+        );
+        assert_eq!(Category::of(&Action::NextOutput), Some(Category::Outputs));
+        assert_eq!(
+            Category::of(&Action::ToggleStacking),
+            Some(Category::Tiling)
+        );
+        assert_eq!(Category::of(&Action::ZoomIn), Some(Category::System));
+        assert_eq!(Category::of(&Action::Close), Some(Category::Windows));
+    }
+
+    #[test]
+    fn escapes_html() {
+        let html = sheet().render(Format::Html);
+        assert!(html.contains("<td>echo &quot;&lt;a&gt;&quot; | tee &amp; wait</td>"));
+        assert!(!html.contains("<a>"));
+    }
+
+    #[test]
+    fn escapes_svg() {
+        let svg = sheet().render(Format::Svg);
+        assert!(svg.contains(">echo &quot;&lt;a&gt;&quot; | tee &amp; wait</text>"));
+        assert!(!svg.contains("<a>"));
+    }
+
+    #[test]
+    fn escapes_markdown_table_cells() {
+        let markdown = sheet().render(Format::Markdown);
+        assert!(markdown.contains("| `Super+\\|` | echo \"<a>\" \\| tee & wait |\n"));
+    }
+}
@@ -0,0 +1,164 @@
+use cosmic_settings_config::{
+    Shortcuts,
+    shortcuts::{Action, Binding},
+};
+
+use super::{
+    action_name,
+    scheme::{self, Conflict, Resolution, Scheme},
+};
+use crate::fl;
+
+/// Asks how the bindings of a scheme that replace current shortcuts are handled.
+#[derive(Debug, Clone)]
+pub struct ResolveShortcutsDialog {
+    pub scheme: Scheme,
+    pub conflicts: Vec<ConflictChoice>,
+    pub choices: Vec<String>,
+    custom: Shortcuts,
+    system: Shortcuts,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConflictChoice {
+    pub binding: Binding,
+    pub action: Action,
+    pub conflict: Conflict,
+    /// One of [`KEEP`], [`REPLACE`] or [`REBIND`], indexing `choices`.
+    pub choice: usize,
+    /// The other binding typed in when rebinding.
+    pub rebind: String,
+}
+
+pub const KEEP: usize = 0;
+pub const REPLACE: usize = 1;
+pub const REBIND: usize = 2;
+
+impl ResolveShortcutsDialog {
+    /// The dialog for a scheme, `None` when nothing conflicts.
+    pub fn new(scheme: Scheme, custom: &Shortcuts, system: &Shortcuts) -> Option<Self> {
+        let conflicts: Vec<ConflictChoice> = scheme::conflicts(&scheme, custom, system)
+            .into_iter()
+            .map(|(binding, action, conflict)| ConflictChoice {
+                // The user's own shortcuts are kept unless they choose otherwise,
+                // the system ones are what schemes are meant to change.
+                choice: match conflict {
+                    Conflict::Custom(_) => KEEP,
+                    Conflict::System(_) => REPLACE,
+                },
+                binding,
+                action,
+                conflict,
+                rebind: String::new(),
+            })
+            .collect();
+
+        (!conflicts.is_empty()).then(|| Self {
+            scheme,
+            conflicts,
+            choices: vec![fl!("keep"), fl!("replace"), fl!("rebind")],
+            custom: custom.clone(),
+            system: system.clone(),
+        })
+    }
+
+    pub fn with_choice(&self, index: usize, choice: usize) -> Self {
+        let mut dialog = self.clone();
+        if let Some(conflict) = dialog.conflicts.get_mut(index) {
+            conflict.choice = choice;
+        }
+        dialog
+    }
+
+    pub fn with_rebind(&self, index: usize, rebind: String) -> Self {
+        let mut dialog = self.clone();
+        if let Some(conflict) = dialog.conflicts.get_mut(index) {
+            conflict.rebind = rebind;
+        }
+        dialog
+    }
+
+    /// Describes the current shortcut a conflict replaces.
+    pub fn current(&self, index: usize) -> String {
+        match &self.conflicts[index].conflict {
+            Conflict::Custom(action) => fl!("replaces-custom", action = action_name(action)),
+            Conflict::System(action) => fl!("replaces-system", action = action_name(action)),
+        }
+    }
+
+    /// The binding typed in to rebind a conflict to, or why it can't be used.
+    pub fn rebind(&self, index: usize) -> Result<Binding, String> {
+        let conflict = &self.conflicts[index];
+        let binding = scheme::parse_binding(&conflict.rebind)
+            .ok_or_else(|| fl!("binding-invalid", binding = conflict.rebind.as_str()))?;
+
+        let taken = self.custom.0.contains_key(&binding)
+            || self.system.0.contains_key(&binding)
+            || self.scheme.shortcuts.contains_key(&binding)
+            || self.conflicts.iter().enumerate().any(|(other, choice)| {
+                other != index
+                    && choice.choice == REBIND
+                    && scheme::parse_binding(&choice.rebind).as_ref() == Some(&binding)
+            });
+        if taken {
+            return Err(fl!("binding-taken", binding = binding.to_string()));
+        }
+        Ok(binding)
+    }
+
+    /// How each conflict is handled, `None` while a rebinding isn't valid.
+    pub fn resolutions(&self) -> Option<Vec<(Binding, Resolution)>> {
+        self.conflicts
+            .iter()
+            .enumerate()
+            .map(|(index, conflict)| {
+                let resolution = match conflict.choice {
+                    KEEP => Resolution::Keep,
+                    REBIND => Resolution::Rebind(self.rebind(index).ok()?),
+                    _ => Resolution::Replace,
+                };
+                Some((conflict.binding.clone(), resolution))
+            })
+            .collect()
+    }
+
+    /// Every shortcut applying the scheme would add or change, as it will be written.
+    pub fn summary(&self) -> Vec<String> {
+        let resolutions = self.resolutions().unwrap_or_default();
+        self.scheme
+            .sorted()
+            .into_iter()
+            .filter_map(|(binding, action)| {
+                let binding = match resolutions
+                    .iter()
+                    .find(|(conflicting, _)| *conflicting == binding)
+                    .map(|(_, resolution)| resolution)
+                {
+                    Some(Resolution::Keep) => return None,
+                    Some(Resolution::Rebind(other)) => other.clone(),
+                    Some(Resolution::Replace) | None => binding,
+                };
+                let to = action_name(&action);
+                match self
+                    .custom
+                    .0
+                    .get(&binding)
+                    .or_else(|| self.system.0.get(&binding))
+                {
+                    Some(current) if *current == action => None,
+                    Some(current) => Some(fl!(
+                        "shortcut-change",
+                        binding = binding.to_string(),
+                        from = action_name(current),
+                        to = to
+                    )),
+                    None => Some(fl!(
+                        "shortcut-add",
+                        binding = binding.to_string(),
+                        action = to
+                    )),
+                }
+            })
+            .collect()
+    }
+}
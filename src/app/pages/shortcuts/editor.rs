@@ -0,0 +1,321 @@
+use cosmic::iced::keyboard::{Key, Modifiers, key::Named};
+use cosmic_settings_config::shortcuts::{Action, Binding};
+
+use super::scheme;
+use crate::fl;
+
+/// What an action takes besides its name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Argument {
+    None,
+    FocusDirection,
+    Direction,
+    Workspace,
+    Orientation,
+    ResizeDirection,
+    System,
+    Command,
+}
+
+impl Argument {
+    fn values(self) -> &'static [&'static str] {
+        match self {
+            Argument::None | Argument::Command => &[],
+            Argument::FocusDirection => &["Left", "Right", "Up", "Down", "In", "Out"],
+            Argument::Direction => &["Left", "Right", "Up", "Down"],
+            Argument::Workspace => &["1", "2", "3", "4", "5", "6", "7", "8", "9"],
+            Argument::Orientation => &["Horizontal", "Vertical"],
+            Argument::ResizeDirection => &["Inwards", "Outwards"],
+            Argument::System => &[
+                "AppLibrary",
+                "BrightnessDown",
+                "BrightnessUp",
+                "DisplayToggle",
+                "HomeFolder",
+                "KeyboardBrightnessDown",
+                "KeyboardBrightnessUp",
+                "Launcher",
+                "LockScreen",
+                "LogOut",
+                "Mute",
+                "MuteMic",
+                "PlayPause",
+                "PlayNext",
+                "PlayPrev",
+                "PowerOff",
+                "Screenshot",
+                "Suspend",
+                "Terminal",
+                "TouchpadToggle",
+                "VolumeLower",
+                "VolumeRaise",
+                "WebBrowser",
+                "WindowSwitcher",
+                "WindowSwitcherPrevious",
+                "WorkspaceOverview",
+            ],
+        }
+    }
+}
+
+/// Every action of `shortcuts::Action` and what it takes.
+const ACTIONS: &[(&str, Argument)] = &[
+    ("Close", Argument::None),
+    ("Disable", Argument::None),
+    ("Focus", Argument::FocusDirection),
+    ("Fullscreen", Argument::None),
+    ("LastWorkspace", Argument::None),
+    ("Maximize", Argument::None),
+    ("Minimize", Argument::None),
+    ("Move", Argument::Direction),
+    ("MoveToLastWorkspace", Argument::None),
+    ("MoveToNextOutput", Argument::None),
+    ("MoveToNextWorkspace", Argument::None),
+    ("MoveToOutput", Argument::Direction),
+    ("MoveToPreviousOutput", Argument::None),
+    ("MoveToPreviousWorkspace", Argument::None),
+    ("MoveToWorkspace", Argument::Workspace),
+    ("MigrateWorkspaceToNextOutput", Argument::None),
+    ("MigrateWorkspaceToOutput", Argument::Direction),
+    ("MigrateWorkspaceToPreviousOutput", Argument::None),
+    ("NextOutput", Argument::None),
+    ("NextWorkspace", Argument::None),
+    ("Orientation", Argument::Orientation),
+    ("PreviousOutput", Argument::None),
+    ("PreviousWorkspace", Argument::None),
+    ("Resizing", Argument::ResizeDirection),
+    ("SendToLastWorkspace", Argument::None),
+    ("SendToNextOutput", Argument::None),
+    ("SendToNextWorkspace", Argument::None),
+    ("SendToOutput", Argument::Direction),
+    ("SendToPreviousOutput", Argument::None),
+    ("SendToPreviousWorkspace", Argument::None),
+    ("SendToWorkspace", Argument::Workspace),
+    ("Spawn", Argument::Command),
+    ("SwapWindow", Argument::None),
+    ("SwitchOutput", Argument::Direction),
+    ("System", Argument::System),
+    ("Terminate", Argument::None),
+    ("ToggleOrientation", Argument::None),
+    ("ToggleStacking", Argument::None),
+    ("ToggleSticky", Argument::None),
+    ("ToggleTiling", Argument::None),
+    ("ToggleWindowFloating", Argument::None),
+    ("Workspace", Argument::Workspace),
+    ("ZoomIn", Argument::None),
+    ("ZoomOut", Argument::None),
+];
+
+/// Builds an action from its name and argument through the format of the
+/// config, so names this version of COSMIC doesn't know are caught.
+fn build(name: &str, argument: Argument, value: &str) -> Option<Action> {
+    match argument {
+        Argument::None => ron::de::from_str(name).ok(),
+        Argument::Command => Some(Action::Spawn(value.to_string())),
+        _ => ron::de::from_str(&format!("{name}({value})")).ok(),
+    }
+}
+
+/// Lists the actions and their arguments for the dropdowns of the editor.
+#[derive(Debug)]
+pub struct ActionPicker {
+    actions: Vec<(&'static str, Argument)>,
+    pub labels: Vec<String>,
+    /// Labels of the arguments of each action, matching `actions`.
+    pub arguments: Vec<Vec<String>>,
+}
+
+impl Default for ActionPicker {
+    fn default() -> Self {
+        let actions: Vec<(&'static str, Argument)> = ACTIONS
+            .iter()
+            .copied()
+            .filter(|(name, argument)| {
+                let value = argument.values().first().copied().unwrap_or_default();
+                build(name, *argument, value).is_some()
+            })
+            .collect();
+        let arguments = actions
+            .iter()
+            .map(|(name, argument)| {
+                argument
+                    .values()
+                    .iter()
+                    .filter(|value| build(name, *argument, value).is_some())
+                    .map(|value| value.to_string())
+                    .collect()
+            })
+            .collect();
+
+        Self {
+            labels: actions.iter().map(|(name, _)| name.to_string()).collect(),
+            actions,
+            arguments,
+        }
+    }
+}
+
+impl ActionPicker {
+    pub fn takes_command(&self, action: usize) -> bool {
+        self.actions
+            .get(action)
+            .is_some_and(|(_, argument)| *argument == Argument::Command)
+    }
+
+    pub fn action(&self, action: usize, argument: usize, command: &str) -> Option<Action> {
+        let (name, kind) = self.actions.get(action)?;
+        let value = match kind {
+            Argument::None => "",
+            Argument::Command => command.trim(),
+            _ => self.arguments[action].get(argument)?.as_str(),
+        };
+        if *kind == Argument::Command && value.is_empty() {
+            return None;
+        }
+        build(name, *kind, value)
+    }
+
+    /// The dropdown indices and command that pick `action`.
+    pub fn select(&self, action: &Action) -> (usize, usize, String) {
+        if let Action::Spawn(command) = action {
+            let index = self
+                .actions
+                .iter()
+                .position(|(_, argument)| *argument == Argument::Command)
+                .unwrap_or_default();
+            return (index, 0, command.clone());
+        }
+
+        let text = ron::ser::to_string(action).unwrap_or_default();
+        let (name, value) = match text.split_once('(') {
+            Some((name, value)) => (name, value.trim_end_matches(')')),
+            None => (text.as_str(), ""),
+        };
+        let index = self
+            .actions
+            .iter()
+            .position(|(known, _)| *known == name)
+            .unwrap_or_default();
+        let argument = self
+            .arguments
+            .get(index)
+            .and_then(|arguments| arguments.iter().position(|known| known == value))
+            .unwrap_or_default();
+        (index, argument, String::new())
+    }
+}
+
+/// A custom shortcut being added or edited.
+#[derive(Debug, Clone, Default)]
+pub struct ShortcutEditor {
+    /// The binding being edited, `None` for a new shortcut.
+    pub original: Option<Binding>,
+    pub binding: Option<Binding>,
+    /// Whether the next key press is taken as the binding.
+    pub capturing: bool,
+    pub action: usize,
+    pub argument: usize,
+    pub command: String,
+    pub error: Option<String>,
+}
+
+impl ShortcutEditor {
+    pub fn new() -> Self {
+        Self {
+            capturing: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn edit(picker: &ActionPicker, binding: Binding, action: &Action) -> Self {
+        let (action, argument, command) = picker.select(action);
+        Self {
+            original: Some(binding.clone()),
+            binding: Some(binding),
+            capturing: false,
+            action,
+            argument,
+            command,
+            error: None,
+        }
+    }
+
+    /// Takes a key press as the binding, ignoring modifiers pressed on their own.
+    ///
+    /// Returns whether capturing is done.
+    pub fn capture(&mut self, modifiers: Modifiers, key: &Key) -> bool {
+        if matches!(key, Key::Named(Named::Escape)) && modifiers.is_empty() {
+            self.capturing = false;
+            return true;
+        }
+        let Some(name) = key_name(key) else {
+            return false;
+        };
+
+        let mut parts = Vec::new();
+        if modifiers.logo() {
+            parts.push("Super");
+        }
+        if modifiers.control() {
+            parts.push("Ctrl");
+        }
+        if modifiers.alt() {
+            parts.push("Alt");
+        }
+        if modifiers.shift() {
+            parts.push("Shift");
+        }
+        parts.push(name.as_str());
+
+        let text = parts.join("+");
+        match scheme::parse_binding(&text) {
+            Some(binding) => {
+                self.binding = Some(binding);
+                self.error = None;
+            }
+            None => self.error = Some(fl!("binding-invalid", binding = text)),
+        }
+        self.capturing = false;
+        true
+    }
+}
+
+/// The xkb name of a key, `None` for modifiers and keys that can't be bound.
+fn key_name(key: &Key) -> Option<String> {
+    let name = match key {
+        Key::Character(c) => return Some(c.to_lowercase()),
+        Key::Named(named) => match named {
+            Named::ArrowLeft => "Left",
+            Named::ArrowRight => "Right",
+            Named::ArrowUp => "Up",
+            Named::ArrowDown => "Down",
+            Named::Enter => "Return",
+            Named::Tab => "Tab",
+            Named::Space => "space",
+            Named::Escape => "Escape",
+            Named::Backspace => "BackSpace",
+            Named::Delete => "Delete",
+            Named::Insert => "Insert",
+            Named::Home => "Home",
+            Named::End => "End",
+            Named::PageUp => "Page_Up",
+            Named::PageDown => "Page_Down",
+            Named::PrintScreen => "Print",
+            Named::F1 => "F1",
+            Named::F2 => "F2",
+            Named::F3 => "F3",
+            Named::F4 => "F4",
+            Named::F5 => "F5",
+            Named::F6 => "F6",
+            Named::F7 => "F7",
+            Named::F8 => "F8",
+            Named::F9 => "F9",
+            Named::F10 => "F10",
+            Named::F11 => "F11",
+            Named::F12 => "F12",
+            _ => return None,
+        },
+        _ => return None,
+    };
+    Some(name.to_string())
+}
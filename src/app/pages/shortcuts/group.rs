@@ -0,0 +1,137 @@
+use std::path::{Path, PathBuf};
+
+use cosmic::Application;
+use cosmic_settings_config::{Shortcuts, shortcuts::Action};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    Error,
+    app::{
+        App,
+        core::versioned::{self, Incompatible, Versioned},
+    },
+};
+
+/// Shortcuts saved by the user or imported from a file, such as the
+/// keybindings a team agreed on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserGroup {
+    #[serde(default)]
+    pub version: u32,
+    pub id: Uuid,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub shortcuts: Shortcuts,
+}
+
+impl UserGroup {
+    pub fn new(name: String, shortcuts: Shortcuts) -> Self {
+        Self {
+            version: Self::VERSION,
+            id: Uuid::new_v4(),
+            name,
+            description: String::new(),
+            shortcuts,
+        }
+    }
+
+    pub fn dir() -> Result<PathBuf, Error> {
+        dirs::data_local_dir()
+            .map(|path| path.join(App::APP_ID).join("shortcuts"))
+            .ok_or(Error::DataDirNotFound)
+    }
+
+    pub fn list() -> Result<(Vec<UserGroup>, Vec<Incompatible>), Error> {
+        Ok(versioned::read_dir(&Self::dir()?))
+    }
+
+    pub fn load(id: Uuid) -> Result<UserGroup, Error> {
+        let path = Self::dir()?.join(id.to_string()).with_extension("ron");
+        versioned::read(&path).map_err(|e| Error::InvalidShortcuts(e.reason))
+    }
+
+    pub fn path(&self) -> Result<PathBuf, Error> {
+        Ok(Self::dir()?.join(self.id.to_string()).with_extension("ron"))
+    }
+
+    pub fn save(&self) -> Result<(), Error> {
+        let dir = Self::dir()?;
+        if !dir.exists() {
+            std::fs::create_dir_all(&dir)?;
+        }
+        self.export(&self.path()?)
+    }
+
+    pub fn delete(&self) -> Result<(), Error> {
+        std::fs::remove_file(self.path()?)?;
+        Ok(())
+    }
+
+    /// Checks that the group can be applied.
+    ///
+    /// Bindings and actions are already checked while the file is read, as
+    /// anything `shortcuts::Binding` and `shortcuts::Action` don't accept
+    /// fails to parse.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.name.trim().is_empty() {
+            return Err(Error::InvalidShortcuts("the group has no name".into()));
+        }
+        if self.shortcuts.0.is_empty() {
+            return Err(Error::InvalidShortcuts("the group has no shortcuts".into()));
+        }
+        for (binding, action) in &self.shortcuts.0 {
+            if let Action::Spawn(command) = action
+                && command.trim().is_empty()
+            {
+                return Err(Error::InvalidShortcuts(format!(
+                    "{binding} runs an empty command"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads a group from a file chosen by the user and stores it in the data dir.
+    ///
+    /// A group whose id is already taken gets a new one, so importing a file
+    /// twice keeps both copies.
+    pub fn import(path: &Path) -> Result<UserGroup, Error> {
+        let contents = std::fs::read_to_string(path)?;
+        let mut group = versioned::from_str::<UserGroup>(&contents)?;
+        group.validate()?;
+
+        if group.path()?.exists() {
+            group.id = Uuid::new_v4();
+        }
+        group.save()?;
+
+        Ok(group)
+    }
+
+    pub fn export(&self, path: &Path) -> Result<(), Error> {
+        let data = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        std::fs::write(path, data)?;
+        Ok(())
+    }
+}
+
+impl Versioned for UserGroup {
+    const VERSION: u32 = 1;
+
+    fn version(&self) -> u32 {
+        self.version
+    }
+
+    fn migrate(contents: &str, version: u32) -> Result<Self, Error> {
+        match version {
+            // Version 0 is the unversioned format, which only lacks the field.
+            0 => Ok(Self {
+                version: Self::VERSION,
+                ..ron::from_str(contents)?
+            }),
+            version => Err(Error::UnsupportedVersion(version)),
+        }
+    }
+}
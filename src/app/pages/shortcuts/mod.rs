@@ -0,0 +1,765 @@
+use std::{io, sync::Arc};
+
+use ashpd::desktop::file_chooser::{FileFilter, SelectedFiles};
+
+use cosmic::{
+    Element, Task,
+    cosmic_config::{self, ConfigGet, ConfigSet, cosmic_config_derive::CosmicConfigEntry},
+    iced::{
+        Alignment,
+        keyboard::{Key, Modifiers},
+    },
+    widget::{
+        self,
+        segmented_button::{self, SingleSelect},
+    },
+};
+use cosmic_settings_config::{
+    Shortcuts,
+    shortcuts::{self, Action, Binding},
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::app::{
+    core::versioned::Incompatible,
+    message::SettingsMessage,
+    page::Page,
+    pages::layouts,
+    report::Report,
+    search::{SearchItem, Target},
+};
+use cheatsheet::{CheatSheet, Format};
+use dialog::ResolveShortcutsDialog;
+use editor::{ActionPicker, ShortcutEditor};
+use group::UserGroup;
+use scheme::{AppliedScheme, Conflict, Resolution, Scheme};
+
+pub mod cheatsheet;
+pub mod dialog;
+pub mod editor;
+pub mod group;
+pub mod scheme;
+
+pub use scheme::ShortcutsGroup;
+
+/// Applies shortcut schemes to the custom shortcuts and removes them again.
+pub struct ShortcutsPage {
+    pub config: cosmic_config::Config,
+    custom: Shortcuts,
+    system: Shortcuts,
+    applied: Vec<AppliedScheme>,
+    schemes: Vec<Scheme>,
+    incompatible: Vec<Incompatible>,
+    selected: usize,
+    model: segmented_button::Model<SingleSelect>,
+    picker: ActionPicker,
+    editor: Option<ShortcutEditor>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tab {
+    Schemes,
+    Custom,
+    CheatSheet,
+}
+
+/// The custom shortcuts of COSMIC Settings, watched to follow edits made there.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, CosmicConfigEntry)]
+#[version = 1]
+pub struct ShortcutsConfig {
+    pub custom: Shortcuts,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Select(usize),
+    ApplyShortcuts(ShortcutsGroup),
+    ApplyResolved(ShortcutsGroup, Vec<(Binding, Resolution)>),
+    RemoveShortcuts(ShortcutsGroup),
+    SetApplied(Vec<AppliedScheme>),
+    TabSelected(segmented_button::Entity),
+    NewShortcut,
+    EditShortcut(Binding),
+    DeleteShortcut(Binding),
+    CaptureBinding,
+    Captured(Modifiers, Key),
+    SetAction(usize),
+    SetArgument(usize),
+    SetCommand(String),
+    SaveShortcut,
+    CancelShortcut,
+    // intercepted in the outer update fn to open the dialog
+    SaveAsGroup,
+    CreateGroup(String),
+    DeleteGroup(Uuid),
+    StartImport,
+    ImportFilePickerResult(Arc<SelectedFiles>),
+    StartExport(Uuid),
+    ExportFilePickerResult(Uuid, Arc<SelectedFiles>),
+    StartCheatSheetExport(Format),
+    CheatSheetFilePickerResult(Format, Arc<SelectedFiles>),
+}
+
+impl ShortcutsPage {
+    pub fn new(applied: Vec<AppliedScheme>) -> Self {
+        let config = shortcuts::context().unwrap();
+        let (schemes, incompatible) = Scheme::list();
+        Self {
+            schemes,
+            incompatible,
+            custom: custom_shortcuts(&config),
+            system: system_shortcuts(&config),
+            config,
+            applied,
+            selected: 0,
+            model: segmented_button::Model::builder()
+                .insert(|b| {
+                    b.text(fl!("shortcut-schemes"))
+                        .data(Tab::Schemes)
+                        .activate()
+                })
+                .insert(|b| b.text(fl!("custom-shortcuts")).data(Tab::Custom))
+                .insert(|b| b.text(fl!("shortcut-cheat-sheet")).data(Tab::CheatSheet))
+                .build(),
+            picker: ActionPicker::default(),
+            editor: None,
+        }
+    }
+
+    pub fn active_tab(&self) -> Tab {
+        self.model
+            .active_data::<Tab>()
+            .copied()
+            .unwrap_or(Tab::Schemes)
+    }
+
+    /// Whether key presses are taken as the binding of the shortcut being edited.
+    pub fn is_capturing(&self) -> bool {
+        self.editor.as_ref().is_some_and(|editor| editor.capturing)
+    }
+
+    /// Takes in the custom shortcuts after they changed on disk.
+    pub fn sync_custom(&mut self, custom: Shortcuts) {
+        self.custom = custom;
+    }
+
+    /// The dialog resolving the conflicts of a scheme, `None` when it has none.
+    pub fn resolve(&self, group: &ShortcutsGroup) -> Option<ResolveShortcutsDialog> {
+        ResolveShortcutsDialog::new(self.scheme(group)?.clone(), &self.custom, &self.system)
+    }
+
+    fn scheme(&self, group: &ShortcutsGroup) -> Option<&Scheme> {
+        self.schemes.iter().find(|scheme| scheme.group == *group)
+    }
+
+    fn reload_schemes(&mut self) {
+        (self.schemes, self.incompatible) = Scheme::list();
+        self.selected = self.selected.min(self.schemes.len().saturating_sub(1));
+    }
+
+    /// The schemes and groups for the global search.
+    pub fn search_items(&self) -> Vec<SearchItem> {
+        self.schemes
+            .iter()
+            .map(|scheme| {
+                SearchItem::new(
+                    Page::Shortcuts,
+                    scheme.name.clone(),
+                    Target::Shortcuts(scheme.group.clone()),
+                )
+            })
+            .collect()
+    }
+
+    /// Previews the scheme the global search jumped to.
+    pub fn show(&mut self, group: &ShortcutsGroup) {
+        if let Some(entity) = self
+            .model
+            .iter()
+            .find(|entity| self.model.data::<Tab>(*entity) == Some(&Tab::Schemes))
+        {
+            self.model.activate(entity);
+        }
+        self.select(group);
+    }
+
+    /// Previews the scheme of a group.
+    fn select(&mut self, group: &ShortcutsGroup) {
+        if let Some(index) = self
+            .schemes
+            .iter()
+            .position(|scheme| scheme.group == *group)
+        {
+            self.selected = index;
+        }
+    }
+
+    fn user_group(&self, id: Uuid) -> Option<UserGroup> {
+        UserGroup::load(id)
+            .map_err(|e| log::error!("Failed to read shortcut group: {e}"))
+            .ok()
+    }
+
+    fn is_applied(&self, group: &ShortcutsGroup) -> bool {
+        self.applied.iter().any(|scheme| scheme.group == *group)
+    }
+
+    /// Writes the shortcut being edited, replacing the one it was opened with.
+    fn save_shortcut(&mut self) -> Task<crate::app::message::Message> {
+        let Some(editor) = &mut self.editor else {
+            return Task::none();
+        };
+        let Some(binding) = editor.binding.clone() else {
+            editor.error = Some(fl!("binding-missing"));
+            return Task::none();
+        };
+        let Some(action) = self
+            .picker
+            .action(editor.action, editor.argument, &editor.command)
+        else {
+            editor.error = Some(fl!("action-invalid"));
+            return Task::none();
+        };
+        if editor.original.as_ref() != Some(&binding) && self.custom.0.contains_key(&binding) {
+            editor.error = Some(fl!("binding-taken", binding = binding.to_string()));
+            return Task::none();
+        }
+
+        let mut custom = self.custom.clone();
+        if let Some(original) = &editor.original {
+            custom.0.remove(original);
+        }
+        custom.0.insert(binding, action);
+        self.editor = None;
+        let applied = self.applied.clone();
+        self.write(custom, applied)
+    }
+
+    /// Writes the custom shortcuts and records the applied schemes.
+    fn write(
+        &mut self,
+        custom: Shortcuts,
+        applied: Vec<AppliedScheme>,
+    ) -> Task<crate::app::message::Message> {
+        if let Err(e) = self.config.set("custom", &custom) {
+            return Report::new(fl!("failed-save-shortcuts"), e).task();
+        }
+        self.custom = custom;
+        self.applied = applied.clone();
+        Task::done(crate::app::message::Message::Settings(
+            SettingsMessage::ShortcutSchemes(applied),
+        ))
+    }
+
+    pub fn update(&mut self, message: Message) -> Task<crate::app::message::Message> {
+        match message {
+            Message::Select(index) => self.selected = index,
+            Message::ApplyShortcuts(group) => {
+                return self.update(Message::ApplyResolved(group, Vec::new()));
+            }
+            Message::ApplyResolved(group, resolutions) => {
+                let Some(scheme) = self.scheme(&group) else {
+                    return Task::none();
+                };
+                let mut custom = self.custom.clone();
+                let mut applied = self.applied.clone();
+                scheme::apply(scheme, &mut custom, &mut applied, &resolutions);
+                return self.write(custom, applied);
+            }
+            Message::RemoveShortcuts(group) => {
+                let mut custom = self.custom.clone();
+                let mut applied = self.applied.clone();
+                scheme::remove(&group, &mut custom, &mut applied);
+                return self.write(custom, applied);
+            }
+            Message::SetApplied(applied) => self.applied = applied,
+            Message::TabSelected(entity) => self.model.activate(entity),
+            Message::NewShortcut => self.editor = Some(ShortcutEditor::new()),
+            Message::EditShortcut(binding) => {
+                if let Some(action) = self.custom.0.get(&binding) {
+                    self.editor = Some(ShortcutEditor::edit(&self.picker, binding, action));
+                }
+            }
+            Message::DeleteShortcut(binding) => {
+                let mut custom = self.custom.clone();
+                if custom.0.remove(&binding).is_some() {
+                    let applied = self.applied.clone();
+                    return self.write(custom, applied);
+                }
+            }
+            Message::CaptureBinding => {
+                if let Some(editor) = &mut self.editor {
+                    editor.capturing = true;
+                }
+            }
+            Message::Captured(modifiers, key) => {
+                if let Some(editor) = &mut self.editor {
+                    editor.capture(modifiers, &key);
+                }
+            }
+            Message::SetAction(action) => {
+                if let Some(editor) = &mut self.editor {
+                    editor.action = action;
+                    editor.argument = 0;
+                    editor.error = None;
+                }
+            }
+            Message::SetArgument(argument) => {
+                if let Some(editor) = &mut self.editor {
+                    editor.argument = argument;
+                }
+            }
+            Message::SetCommand(command) => {
+                if let Some(editor) = &mut self.editor {
+                    editor.command = command;
+                    editor.error = None;
+                }
+            }
+            Message::SaveShortcut => return self.save_shortcut(),
+            Message::CancelShortcut => self.editor = None,
+            Message::SaveAsGroup => {}
+            Message::CreateGroup(name) => {
+                let group = UserGroup::new(name, self.custom.clone());
+                match group.validate().and_then(|_| group.save()) {
+                    Ok(()) => {
+                        self.reload_schemes();
+                        self.select(&ShortcutsGroup::User(group.id));
+                    }
+                    Err(e) => return Report::new(fl!("failed-save-shortcut-group"), e).task(),
+                }
+            }
+            Message::DeleteGroup(id) => {
+                if let Some(group) = self.user_group(id) {
+                    let deleted = group.delete();
+                    self.reload_schemes();
+                    if let Err(e) = deleted {
+                        return Report::new(fl!("failed-delete-shortcut-group"), e).task();
+                    }
+                }
+            }
+            Message::StartImport => {
+                return Task::perform(
+                    async {
+                        SelectedFiles::open_file()
+                            .modal(true)
+                            .filter(FileFilter::glob(FileFilter::new("ron"), "*.ron"))
+                            .send()
+                            .await?
+                            .response()
+                    },
+                    |res| match res {
+                        Ok(files) => crate::app::message::Message::Shortcuts(
+                            Message::ImportFilePickerResult(Arc::new(files)),
+                        ),
+                        Err(e) => crate::app::message::Message::Error(Report::new(
+                            fl!("failed-import-shortcuts"),
+                            e,
+                        )),
+                    },
+                );
+            }
+            Message::ImportFilePickerResult(files) => {
+                match layouts::selected_path(&files).and_then(|path| UserGroup::import(&path)) {
+                    Ok(group) => {
+                        self.reload_schemes();
+                        self.select(&ShortcutsGroup::User(group.id));
+                    }
+                    Err(e) => return Report::new(fl!("failed-import-shortcuts"), e).task(),
+                }
+            }
+            Message::StartExport(id) => {
+                if let Some(group) = self.user_group(id) {
+                    let name = format!("{}.ron", group.name);
+                    return Task::perform(
+                        async move {
+                            SelectedFiles::save_file()
+                                .modal(true)
+                                .current_name(name.as_str())
+                                .filter(FileFilter::glob(FileFilter::new("ron"), "*.ron"))
+                                .send()
+                                .await?
+                                .response()
+                        },
+                        move |res| match res {
+                            Ok(files) => crate::app::message::Message::Shortcuts(
+                                Message::ExportFilePickerResult(id, Arc::new(files)),
+                            ),
+                            Err(e) => crate::app::message::Message::Error(Report::new(
+                                fl!("failed-export-shortcuts"),
+                                e,
+                            )),
+                        },
+                    );
+                }
+            }
+            Message::ExportFilePickerResult(id, files) => {
+                if let Some(group) = self.user_group(id)
+                    && let Err(e) =
+                        layouts::selected_path(&files).and_then(|path| group.export(&path))
+                {
+                    return Report::new(fl!("failed-export-shortcuts"), e).task();
+                }
+            }
+            Message::StartCheatSheetExport(format) => {
+                let name = format!("{}.{}", fl!("shortcut-cheat-sheet"), format.extension());
+                return Task::perform(
+                    async move {
+                        SelectedFiles::save_file()
+                            .modal(true)
+                            .current_name(name.as_str())
+                            .filter(FileFilter::glob(
+                                FileFilter::new(format.name()),
+                                &format!("*.{}", format.extension()),
+                            ))
+                            .send()
+                            .await?
+                            .response()
+                    },
+                    move |res| match res {
+                        Ok(files) => crate::app::message::Message::Shortcuts(
+                            Message::CheatSheetFilePickerResult(format, Arc::new(files)),
+                        ),
+                        Err(e) => crate::app::message::Message::Error(Report::new(
+                            fl!("failed-export-cheat-sheet"),
+                            e,
+                        )),
+                    },
+                );
+            }
+            Message::CheatSheetFilePickerResult(format, files) => {
+                let contents = CheatSheet::new(&self.custom, &self.system).render(format);
+                if let Err(e) = layouts::selected_path(&files)
+                    .and_then(|path| std::fs::write(path, contents).map_err(Into::into))
+                {
+                    return Report::new(fl!("failed-export-cheat-sheet"), e).task();
+                }
+            }
+        }
+        Task::none()
+    }
+
+    pub fn view<'a>(&'a self) -> Element<'a, Message> {
+        let spacing = cosmic::theme::spacing();
+
+        let tabs = widget::segmented_button::horizontal(&self.model)
+            .padding(spacing.space_xxxs)
+            .button_alignment(Alignment::Center)
+            .on_activate(Message::TabSelected);
+
+        let content = match self.active_tab() {
+            Tab::Schemes => self.schemes(),
+            Tab::Custom => self.custom(),
+            Tab::CheatSheet => self.cheat_sheet(),
+        };
+
+        widget::column()
+            .push(tabs)
+            .push(content)
+            .spacing(spacing.space_xxs)
+            .into()
+    }
+
+    fn cheat_sheet<'a>(&'a self) -> Element<'a, Message> {
+        let spacing = cosmic::theme::spacing();
+
+        let export = Format::ALL.into_iter().fold(
+            widget::row()
+                .push(widget::text::body(fl!("export-cheat-sheet")))
+                .push(widget::horizontal_space())
+                .spacing(spacing.space_xxs)
+                .align_y(Alignment::Center),
+            |row, format| {
+                row.push(
+                    widget::button::standard(format.name())
+                        .on_press(Message::StartCheatSheetExport(format)),
+                )
+            },
+        );
+
+        let sheet = CheatSheet::new(&self.custom, &self.system);
+        let categories = sheet.categories.into_iter().fold(
+            widget::column().spacing(spacing.space_m),
+            |column, (category, shortcuts)| {
+                column.push(shortcuts.into_iter().fold(
+                    widget::settings::section().title(category.name()),
+                    |section, (binding, action)| {
+                        section.add(
+                            widget::settings::item::builder(binding)
+                                .control(widget::text::body(action)),
+                        )
+                    },
+                ))
+            },
+        );
+
+        widget::scrollable(
+            widget::column()
+                .push(export)
+                .push(categories)
+                .spacing(spacing.space_m),
+        )
+        .into()
+    }
+
+    fn custom<'a>(&'a self) -> Element<'a, Message> {
+        let spacing = cosmic::theme::spacing();
+
+        let mut shortcuts: Vec<(&Binding, &Action)> = self.custom.0.iter().collect();
+        shortcuts.sort_by_cached_key(|(binding, _)| binding.to_string());
+
+        let list = shortcuts.into_iter().fold(
+            widget::settings::section().title(fl!("custom-shortcuts")),
+            |section, (binding, action)| {
+                section.add(
+                    widget::settings::item::builder(binding.to_string())
+                        .description(action_name(action))
+                        .control(
+                            widget::row()
+                                .push(
+                                    widget::button::icon(widget::icon::from_name("edit-symbolic"))
+                                        .on_press(Message::EditShortcut(binding.clone())),
+                                )
+                                .push(
+                                    widget::button::icon(widget::icon::from_name(
+                                        "user-trash-symbolic",
+                                    ))
+                                    .class(cosmic::style::Button::Destructive)
+                                    .on_press(Message::DeleteShortcut(binding.clone())),
+                                )
+                                .spacing(spacing.space_xxs),
+                        ),
+                )
+            },
+        );
+
+        let editor = match &self.editor {
+            Some(editor) => self.editor(editor),
+            None => widget::row()
+                .push(widget::horizontal_space())
+                .push(
+                    widget::button::standard(fl!("save-as-group")).on_press_maybe(
+                        (!self.custom.0.is_empty()).then_some(Message::SaveAsGroup),
+                    ),
+                )
+                .push(widget::button::suggested(fl!("add-shortcut")).on_press(Message::NewShortcut))
+                .spacing(spacing.space_xxs)
+                .into(),
+        };
+
+        widget::scrollable(
+            widget::column()
+                .push(editor)
+                .push(list)
+                .spacing(spacing.space_m),
+        )
+        .into()
+    }
+
+    fn editor<'a>(&'a self, editor: &'a ShortcutEditor) -> Element<'a, Message> {
+        let binding = if editor.capturing {
+            widget::button::standard(fl!("press-keys"))
+        } else {
+            widget::button::standard(
+                editor
+                    .binding
+                    .as_ref()
+                    .map(|binding| binding.to_string())
+                    .unwrap_or_else(|| fl!("set-binding")),
+            )
+            .on_press(Message::CaptureBinding)
+        };
+
+        let arguments = self.picker.arguments.get(editor.action);
+        let argument = if self.picker.takes_command(editor.action) {
+            Some(
+                widget::settings::item::builder(fl!("command")).control(
+                    widget::text_input(fl!("command"), editor.command.as_str())
+                        .on_input(Message::SetCommand)
+                        .on_submit(|_| Message::SaveShortcut),
+                ),
+            )
+        } else {
+            arguments
+                .filter(|arguments| !arguments.is_empty())
+                .map(|arguments| {
+                    widget::settings::item::builder(fl!("argument")).control(widget::dropdown(
+                        arguments,
+                        Some(editor.argument),
+                        Message::SetArgument,
+                    ))
+                })
+        };
+
+        let title = if editor.original.is_some() {
+            fl!("edit-shortcut")
+        } else {
+            fl!("add-shortcut")
+        };
+
+        let mut section = widget::settings::section()
+            .title(title)
+            .add(
+                widget::settings::item::builder(fl!("binding"))
+                    .description(editor.error.clone().unwrap_or_default())
+                    .control(binding),
+            )
+            .add(
+                widget::settings::item::builder(fl!("action")).control(widget::dropdown(
+                    &self.picker.labels,
+                    Some(editor.action),
+                    Message::SetAction,
+                )),
+            );
+        if let Some(argument) = argument {
+            section = section.add(argument);
+        }
+
+        section
+            .add(
+                widget::row()
+                    .push(widget::horizontal_space())
+                    .push(widget::button::standard(fl!("cancel")).on_press(Message::CancelShortcut))
+                    .push(widget::button::suggested(fl!("save")).on_press(Message::SaveShortcut))
+                    .spacing(cosmic::theme::spacing().space_xxs),
+            )
+            .into()
+    }
+
+    fn schemes<'a>(&'a self) -> Element<'a, Message> {
+        let spacing = cosmic::theme::spacing();
+
+        let incompatible = (!self.incompatible.is_empty()).then(|| {
+            self.incompatible.iter().fold(
+                widget::settings::section().title(fl!("incompatible-shortcut-groups")),
+                |section, incompatible| {
+                    section.add(
+                        widget::settings::item::builder(incompatible.name.clone())
+                            .description(incompatible.reason.clone()),
+                    )
+                },
+            )
+        });
+
+        let schemes = self.schemes.iter().enumerate().fold(
+            widget::settings::section().title(fl!("shortcut-schemes")),
+            |section, (index, scheme)| {
+                let applied = self.is_applied(&scheme.group);
+                let user = match scheme.group {
+                    ShortcutsGroup::User(id) => Some(
+                        widget::row()
+                            .push(
+                                widget::button::icon(widget::icon::from_name(
+                                    "document-save-symbolic",
+                                ))
+                                .on_press(Message::StartExport(id)),
+                            )
+                            .push(
+                                widget::button::icon(widget::icon::from_name(
+                                    "user-trash-symbolic",
+                                ))
+                                .class(cosmic::style::Button::Destructive)
+                                .on_press_maybe((!applied).then_some(Message::DeleteGroup(id))),
+                            )
+                            .spacing(spacing.space_xxs),
+                    ),
+                    _ => None,
+                };
+                section.add(
+                    widget::settings::item::builder(scheme.name.clone())
+                        .description(scheme.description.clone())
+                        .control(
+                            widget::row()
+                                .push_maybe(applied.then(|| widget::text::body(fl!("applied"))))
+                                .push(widget::button::standard(fl!("preview")).on_press_maybe(
+                                    (index != self.selected).then_some(Message::Select(index)),
+                                ))
+                                .push_maybe(user)
+                                .spacing(spacing.space_xs)
+                                .align_y(Alignment::Center),
+                        ),
+                )
+            },
+        );
+
+        let actions = widget::row()
+            .push(widget::horizontal_space())
+            .push(widget::button::standard(fl!("import-group")).on_press(Message::StartImport))
+            .push_maybe(self.schemes.get(self.selected).map(|scheme| {
+                if self.is_applied(&scheme.group) {
+                    widget::button::destructive(fl!("remove-scheme"))
+                        .on_press(Message::RemoveShortcuts(scheme.group.clone()))
+                } else {
+                    widget::button::suggested(fl!("apply-scheme"))
+                        .on_press(Message::ApplyShortcuts(scheme.group.clone()))
+                }
+            }))
+            .spacing(spacing.space_xxs);
+
+        let bindings = self.schemes.get(self.selected).map(|scheme| {
+            scheme.sorted().into_iter().fold(
+                widget::settings::section().title(scheme.name.clone()),
+                |section, (binding, action)| {
+                    let conflict =
+                        match scheme::conflict(&binding, &action, &self.custom, &self.system) {
+                            Some(Conflict::Custom(current)) => {
+                                fl!("replaces-custom", action = action_name(&current))
+                            }
+                            Some(Conflict::System(current)) => {
+                                fl!("replaces-system", action = action_name(&current))
+                            }
+                            None => String::new(),
+                        };
+                    section.add(
+                        widget::settings::item::builder(binding.to_string())
+                            .description(conflict)
+                            .control(widget::text::body(action_name(&action))),
+                    )
+                },
+            )
+        });
+
+        widget::scrollable(
+            widget::column()
+                .push(widget::text::body(fl!("shortcuts-description")))
+                .push_maybe(incompatible)
+                .push(schemes)
+                .push(actions)
+                .push_maybe(bindings)
+                .spacing(spacing.space_m),
+        )
+        .into()
+    }
+}
+
+/// A readable name for an action, such as `Focus(Left)` or `Launcher`.
+pub fn action_name(action: &Action) -> String {
+    match action {
+        Action::System(action) => format!("{action:?}"),
+        Action::Spawn(command) => command.clone(),
+        action => format!("{action:?}"),
+    }
+}
+
+/// Reads the user's custom shortcuts, which are unset on a fresh install.
+pub fn custom_shortcuts(config: &cosmic_config::Config) -> Shortcuts {
+    read_shortcuts(config, "custom")
+}
+
+/// Reads the shortcuts COSMIC ships with.
+pub fn system_shortcuts(config: &cosmic_config::Config) -> Shortcuts {
+    read_shortcuts(config, "defaults")
+}
+
+fn read_shortcuts(config: &cosmic_config::Config, key: &str) -> Shortcuts {
+    match config.get::<Shortcuts>(key) {
+        Ok(shortcuts) => shortcuts,
+        Err(cosmic_config::Error::GetKey(_, e)) if e.kind() == io::ErrorKind::NotFound => {
+            Shortcuts::default()
+        }
+        Err(e) => {
+            error!("unable to get the {key} shortcuts config: {e}");
+            Shortcuts::default()
+        }
+    }
+}
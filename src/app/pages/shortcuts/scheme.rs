@@ -0,0 +1,345 @@
+use std::collections::HashMap;
+
+use cosmic_settings_config::{
+    Shortcuts,
+    shortcuts::{Action, Binding},
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::group::UserGroup;
+use crate::app::core::versioned::Incompatible;
+use crate::fl;
+
+/// Identifies a set of bindings, either bundled and modelled on another
+/// desktop or a group the user saved or imported.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ShortcutsGroup {
+    Windows,
+    MacOs,
+    Gnome,
+    Kde,
+    Tiling,
+    User(Uuid),
+}
+
+impl ShortcutsGroup {
+    pub const BUNDLED: [ShortcutsGroup; 5] = [
+        ShortcutsGroup::Windows,
+        ShortcutsGroup::MacOs,
+        ShortcutsGroup::Gnome,
+        ShortcutsGroup::Kde,
+        ShortcutsGroup::Tiling,
+    ];
+}
+
+/// The bindings of a group, loaded once to be previewed and applied.
+#[derive(Debug, Clone)]
+pub struct Scheme {
+    pub group: ShortcutsGroup,
+    pub name: String,
+    pub description: String,
+    pub shortcuts: HashMap<Binding, Action>,
+}
+
+impl Scheme {
+    /// The schemes shipped with Tweaks.
+    pub fn bundled() -> Vec<Scheme> {
+        ShortcutsGroup::BUNDLED
+            .iter()
+            .filter_map(Self::load_bundled)
+            .collect()
+    }
+
+    /// Lists the bundled schemes followed by the user's groups.
+    pub fn list() -> (Vec<Scheme>, Vec<Incompatible>) {
+        let mut schemes = Self::bundled();
+        let (groups, incompatible) = match UserGroup::list() {
+            Ok(list) => list,
+            Err(e) => {
+                log::error!("Failed to list shortcut groups: {e}");
+                Default::default()
+            }
+        };
+        schemes.extend(groups.into_iter().map(Scheme::from));
+        (schemes, incompatible)
+    }
+
+    /// Loads the scheme of a group, `None` when a user group is gone.
+    pub fn load(group: &ShortcutsGroup) -> Option<Scheme> {
+        match group {
+            ShortcutsGroup::User(id) => UserGroup::load(*id)
+                .map_err(|e| log::error!("Failed to read shortcut group {id}: {e}"))
+                .ok()
+                .map(Scheme::from),
+            group => Self::load_bundled(group),
+        }
+    }
+
+    fn load_bundled(group: &ShortcutsGroup) -> Option<Scheme> {
+        let (name, contents) = Self::bundled_source(group)?;
+        let description = match group {
+            ShortcutsGroup::Windows => fl!("windows-desc"),
+            ShortcutsGroup::MacOs => fl!("macos-desc"),
+            ShortcutsGroup::Gnome => fl!("gnome-desc"),
+            ShortcutsGroup::Kde => fl!("kde-desc"),
+            ShortcutsGroup::Tiling => fl!("tiling-desc"),
+            ShortcutsGroup::User(_) => return None,
+        };
+
+        let shortcuts = ron::de::from_str(contents)
+            .map_err(|e| log::error!("Failed to read the {name} shortcuts: {e}"))
+            .ok()?;
+        Some(Scheme {
+            group: group.clone(),
+            name: name.to_string(),
+            description,
+            shortcuts,
+        })
+    }
+
+    /// The display name and embedded RON of a bundled group.
+    fn bundled_source(group: &ShortcutsGroup) -> Option<(&'static str, &'static str)> {
+        match group {
+            ShortcutsGroup::Windows => Some((
+                "Windows",
+                include_str!("../../../../res/shortcuts/windows.ron"),
+            )),
+            ShortcutsGroup::MacOs => {
+                Some(("macOS", include_str!("../../../../res/shortcuts/macos.ron")))
+            }
+            ShortcutsGroup::Gnome => {
+                Some(("GNOME", include_str!("../../../../res/shortcuts/gnome.ron")))
+            }
+            ShortcutsGroup::Kde => Some((
+                "KDE Plasma",
+                include_str!("../../../../res/shortcuts/kde.ron"),
+            )),
+            ShortcutsGroup::Tiling => Some((
+                "i3 / Sway",
+                include_str!("../../../../res/shortcuts/tiling.ron"),
+            )),
+            ShortcutsGroup::User(_) => None,
+        }
+    }
+
+    /// The bindings of the scheme, sorted for display.
+    pub fn sorted(&self) -> Vec<(Binding, Action)> {
+        let mut shortcuts: Vec<(Binding, Action)> = self
+            .shortcuts
+            .iter()
+            .map(|(binding, action)| (binding.clone(), action.clone()))
+            .collect();
+        shortcuts.sort_by_cached_key(|(binding, _)| binding.to_string());
+        shortcuts
+    }
+}
+
+impl From<UserGroup> for Scheme {
+    fn from(group: UserGroup) -> Self {
+        Scheme {
+            group: ShortcutsGroup::User(group.id),
+            name: group.name,
+            description: group.description,
+            shortcuts: group.shortcuts.0,
+        }
+    }
+}
+
+/// A scheme that was applied, recorded so it can be removed later.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AppliedScheme {
+    pub group: ShortcutsGroup,
+    /// The bindings the scheme set, which differ from its own when some were
+    /// kept or rebound.
+    pub bindings: Vec<(Binding, Action)>,
+    /// Custom bindings the scheme replaced, put back when it is removed.
+    pub replaced: Vec<(Binding, Action)>,
+}
+
+/// How a binding of a scheme that conflicts with a current shortcut is handled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Resolution {
+    /// Keeps the current shortcut and leaves out the one of the scheme.
+    Keep,
+    /// Replaces the current shortcut with the one of the scheme.
+    Replace,
+    /// Binds the action of the scheme to another binding.
+    Rebind(Binding),
+}
+
+/// A shortcut that applying a binding would replace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Conflict {
+    Custom(Action),
+    System(Action),
+}
+
+/// What a binding of a scheme would replace, if anything.
+pub fn conflict(
+    binding: &Binding,
+    action: &Action,
+    custom: &Shortcuts,
+    system: &Shortcuts,
+) -> Option<Conflict> {
+    if let Some(current) = custom.0.get(binding) {
+        return (current != action).then(|| Conflict::Custom(current.clone()));
+    }
+    system
+        .0
+        .get(binding)
+        .filter(|current| *current != action)
+        .map(|current| Conflict::System(current.clone()))
+}
+
+/// Every binding of a scheme that would replace a current shortcut.
+pub fn conflicts(
+    scheme: &Scheme,
+    custom: &Shortcuts,
+    system: &Shortcuts,
+) -> Vec<(Binding, Action, Conflict)> {
+    scheme
+        .sorted()
+        .into_iter()
+        .filter_map(|(binding, action)| {
+            let conflict = conflict(&binding, &action, custom, system)?;
+            Some((binding, action, conflict))
+        })
+        .collect()
+}
+
+/// Reads a binding written like `Super+Shift+K`.
+///
+/// Goes through the same format as the bundled schemes, so key names are
+/// those of xkb, such as `Left`, `Return` or `Page_Up`.
+pub fn parse_binding(text: &str) -> Option<Binding> {
+    let mut parts: Vec<&str> = text.split('+').map(str::trim).collect();
+    let key = parts.pop().filter(|key| !key.is_empty())?;
+    if key.contains(['"', '\\']) {
+        return None;
+    }
+    // Letters are bound by their lowercase keysym, Shift is a modifier of its own.
+    let key = if key.len() == 1 {
+        key.to_lowercase()
+    } else {
+        key.to_string()
+    };
+
+    let modifiers = parts
+        .iter()
+        .map(|modifier| match modifier.to_lowercase().as_str() {
+            "super" | "logo" | "meta" => Some("Super"),
+            "ctrl" | "control" => Some("Ctrl"),
+            "alt" => Some("Alt"),
+            "shift" => Some("Shift"),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    ron::de::from_str(&format!(
+        "(modifiers: [{}], key: \"{key}\")",
+        modifiers.join(", ")
+    ))
+    .ok()
+}
+
+/// Adds the bindings of a scheme to the custom shortcuts.
+///
+/// Conflicting bindings are handled as `resolutions` says, those left out
+/// replace the current shortcut. A scheme applied again is removed first, so
+/// it is never recorded twice.
+pub fn apply(
+    scheme: &Scheme,
+    custom: &mut Shortcuts,
+    applied: &mut Vec<AppliedScheme>,
+    resolutions: &[(Binding, Resolution)],
+) {
+    remove(&scheme.group, custom, applied);
+
+    let mut bindings = Vec::new();
+    let mut replaced = Vec::new();
+    for (binding, action) in scheme.shortcuts.clone() {
+        let binding = match resolutions
+            .iter()
+            .find(|(conflicting, _)| *conflicting == binding)
+            .map(|(_, resolution)| resolution)
+        {
+            Some(Resolution::Keep) => continue,
+            Some(Resolution::Rebind(other)) => other.clone(),
+            Some(Resolution::Replace) | None => binding,
+        };
+        if let Some(previous) = custom.0.insert(binding.clone(), action.clone())
+            && previous != action
+        {
+            replaced.push((binding.clone(), previous));
+        }
+        bindings.push((binding, action));
+    }
+
+    applied.push(AppliedScheme {
+        group: scheme.group.clone(),
+        bindings,
+        replaced,
+    });
+}
+
+/// Takes the bindings of a scheme out of the custom shortcuts.
+///
+/// Bindings changed since the scheme was applied are left alone. The ones it
+/// replaced are put back, or handed to the scheme that replaced them in turn.
+pub fn remove(group: &ShortcutsGroup, custom: &mut Shortcuts, applied: &mut Vec<AppliedScheme>) {
+    let Some(index) = applied.iter().position(|scheme| scheme.group == *group) else {
+        return;
+    };
+    let scheme = applied.remove(index);
+
+    for (binding, action) in &scheme.bindings {
+        let previous = scheme
+            .replaced
+            .iter()
+            .find(|(replaced, _)| replaced == binding)
+            .map(|(_, action)| action.clone());
+
+        if custom.0.get(binding) == Some(action) {
+            match previous {
+                Some(previous) => custom.0.insert(binding.clone(), previous),
+                None => custom.0.remove(binding),
+            };
+            continue;
+        }
+
+        // A later scheme took the binding over, it now restores what this one replaced.
+        for other in applied.iter_mut() {
+            let Some(position) = other
+                .replaced
+                .iter()
+                .position(|(replaced, replaced_action)| {
+                    replaced == binding && replaced_action == action
+                })
+            else {
+                continue;
+            };
+            match &previous {
+                Some(previous) => other.replaced[position].1 = previous.clone(),
+                None => {
+                    other.replaced.remove(position);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_schemes_parse() {
+        for group in &ShortcutsGroup::BUNDLED {
+            let (name, contents) = Scheme::bundled_source(group).unwrap();
+            let shortcuts: HashMap<Binding, Action> = ron::de::from_str(contents)
+                .unwrap_or_else(|e| panic!("the {name} shortcuts do not parse: {e}"));
+            assert!(!shortcuts.is_empty(), "the {name} shortcuts are empty");
+        }
+    }
+}
@@ -1,14 +1,20 @@
 use std::{fmt::Display, path::PathBuf};
 
-use crate::{app::App, fl};
+use crate::{
+    Error,
+    app::{App, core::versioned::Versioned},
+    fl,
+};
 use chrono::{NaiveDateTime, Utc};
 use cosmic::Application;
-use cosmic_ext_config_templates::{Schema, panel::PanelSchema};
+use cosmic_ext_config_templates::{Schema, load_template, panel::PanelSchema};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 #[derive(Debug, Serialize, Clone, Default, Deserialize)]
 pub struct Snapshot {
+    #[serde(default)]
+    pub version: u32,
     pub id: Uuid,
     pub name: String,
     pub kind: SnapshotKind,
@@ -38,6 +44,7 @@ impl Snapshot {
         let created = Utc::now().naive_local();
 
         Self {
+            version: Self::VERSION,
             id,
             name: name.to_string(),
             kind,
@@ -50,12 +57,51 @@ impl Snapshot {
         self.created.format("%Y-%m-%d %H:%M:%S").to_string()
     }
 
-    pub fn path(&self) -> PathBuf {
+    /// Writes the snapshot to the snapshots directory.
+    pub fn save(&self) -> Result<(), Error> {
+        std::fs::create_dir_all(Self::dir()?)?;
+        std::fs::write(self.path()?, ron::to_string(self)?)?;
+        log::info!("Snapshot created: {}", self.name);
+        Ok(())
+    }
+
+    /// Loads the panels of the snapshot back.
+    pub fn restore(&self) -> Result<(), Error> {
+        match self.schema.clone() {
+            Some(schema) => load_template(schema).map_err(|e| Error::Template(e.to_string())),
+            None => {
+                log::warn!("Snapshot does not contain a valid schema.");
+                Ok(())
+            }
+        }
+    }
+
+    pub fn path(&self) -> Result<PathBuf, Error> {
+        Ok(Self::dir()?.join(self.id.to_string()).with_extension("ron"))
+    }
+
+    pub fn dir() -> Result<PathBuf, Error> {
         dirs::data_local_dir()
-            .expect("Failed to get data directory")
-            .join(App::APP_ID)
-            .join("snapshots")
-            .join(self.id.to_string())
-            .with_extension("ron")
+            .map(|path| path.join(App::APP_ID).join("snapshots"))
+            .ok_or(Error::DataDirNotFound)
+    }
+}
+
+impl Versioned for Snapshot {
+    const VERSION: u32 = 1;
+
+    fn version(&self) -> u32 {
+        self.version
+    }
+
+    fn migrate(contents: &str, version: u32) -> Result<Self, Error> {
+        match version {
+            // Version 0 is the unversioned format, which only lacks the field.
+            0 => Ok(Self {
+                version: Self::VERSION,
+                ..ron::from_str(contents)?
+            }),
+            version => Err(Error::UnsupportedVersion(version)),
+        }
     }
 }
@@ -1,32 +1,53 @@
+use std::path::PathBuf;
+
 use config::Snapshot;
-use cosmic::{Application, Element, Task, iced::Length, widget};
-use cosmic_ext_config_templates::load_template;
-use dirs::data_local_dir;
+use cosmic::{Element, Task, iced::Length, widget};
+use uuid::Uuid;
 
+use crate::app::core::{
+    style,
+    versioned::{self, Incompatible},
+};
+use crate::app::page::Page;
 use crate::app::pages::snapshots::config::SnapshotKind;
+use crate::app::report::Report;
+use crate::app::search::{SearchItem, Target};
 use crate::icon_handle;
-use crate::{app::App, fl};
+use crate::{Error, fl};
 
 pub mod config;
 
 #[derive(Debug, Default)]
 pub struct Snapshots {
     snapshots: Vec<Snapshot>,
+    incompatible: Vec<Incompatible>,
+    /// The snapshot the global search jumped to.
+    highlight: Option<String>,
 }
 
 impl Snapshots {
-    pub fn list() -> Vec<Snapshot> {
-        dirs::data_local_dir()
-            .expect("Failed to get data directory")
-            .join(App::APP_ID)
-            .join("snapshots")
-            .read_dir()
-            .expect("Failed to read snapshots directory")
-            .filter_map(|entry| entry.ok())
-            .filter_map(|entry| std::fs::read_to_string(entry.path()).ok())
-            .filter_map(|entry| ron::from_str(&entry).ok())
+    pub fn list() -> Result<(Vec<Snapshot>, Vec<Incompatible>), Error> {
+        Ok(versioned::read_dir(&Snapshot::dir()?))
+    }
+
+    /// The snapshots for the global search.
+    pub fn search_items(&self) -> Vec<SearchItem> {
+        self.snapshots
+            .iter()
+            .map(|snapshot| {
+                SearchItem::new(
+                    Page::Snapshots,
+                    snapshot.name.clone(),
+                    Target::Snapshot(snapshot.name.clone()),
+                )
+            })
             .collect()
     }
+
+    /// Outlines a snapshot the global search jumped to, `None` clearing it.
+    pub fn set_highlight(&mut self, name: Option<String>) {
+        self.highlight = name;
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -35,6 +56,12 @@ pub enum Message {
     ReloadSnapshots,
     RestoreSnapshot(Snapshot),
     DeleteSnapshot(Snapshot),
+    DeleteIncompatible(PathBuf),
+    DeleteIncompatibleConfirmed(PathBuf),
+    /// A snapshot was created or deleted, announced in the outer update fn.
+    Changed,
+    /// A snapshot was restored, announced in the outer update fn.
+    Restored(Uuid),
 }
 
 impl Snapshots {
@@ -43,8 +70,8 @@ impl Snapshots {
         let snapshots = self
             .snapshots
             .iter()
-            .map(|snapshot| {
-                widget::settings::item_row(vec![
+            .map(|snapshot| -> Element<'a, Message> {
+                let row = widget::settings::item_row(vec![
                     widget::text(&snapshot.name)
                         .width(Length::FillPortion(2))
                         .into(),
@@ -79,9 +106,53 @@ impl Snapshots {
                 ])
                 .align_y(cosmic::iced::Alignment::Center)
                 .spacing(spacing.space_xxxs)
+                .width(Length::Fill);
+                if self.highlight.as_ref() == Some(&snapshot.name) {
+                    widget::container(row)
+                        .class(cosmic::theme::Container::custom(style::highlight))
+                        .into()
+                } else {
+                    row.into()
+                }
+            })
+            .chain(self.incompatible.iter().map(|incompatible| {
+                widget::settings::item_row(vec![
+                    widget::column()
+                        .push(widget::text(&incompatible.name))
+                        .push(
+                            widget::text::caption(fl!("incompatible-snapshot"))
+                                .class(cosmic::style::Text::Accent),
+                        )
+                        .width(Length::FillPortion(2))
+                        .into(),
+                    widget::text(fl!("version", version = incompatible.version))
+                        .width(Length::FillPortion(1))
+                        .into(),
+                    widget::container(widget::tooltip(
+                        widget::text::caption(incompatible.reason.clone()),
+                        widget::text(incompatible.path.display().to_string()),
+                        widget::tooltip::Position::Bottom,
+                    ))
+                    .width(Length::FillPortion(1))
+                    .into(),
+                    widget::row()
+                        .push(widget::tooltip(
+                            widget::button::icon(icon_handle!("user-trash-symbolic", 14))
+                                .class(cosmic::style::Button::Destructive)
+                                .on_press(Message::DeleteIncompatible(incompatible.path.clone())),
+                            widget::text(fl!("delete-snapshot")),
+                            widget::tooltip::Position::Bottom,
+                        ))
+                        .align_y(cosmic::iced::Alignment::Center)
+                        .spacing(spacing.space_xxs)
+                        .width(Length::FillPortion(1))
+                        .into(),
+                ])
+                .align_y(cosmic::iced::Alignment::Center)
+                .spacing(spacing.space_xxxs)
                 .width(Length::Fill)
                 .into()
-            })
+            }))
             .collect::<Vec<Element<Message>>>();
 
         let heading_item = |name, width| {
@@ -131,7 +202,10 @@ impl Snapshots {
         let mut tasks = vec![];
         match message {
             Message::ReloadSnapshots => {
-                self.snapshots = Snapshots::list();
+                match Snapshots::list() {
+                    Ok(list) => (self.snapshots, self.incompatible) = list,
+                    Err(e) => return Report::new(fl!("failed-load-snapshots"), e).task(),
+                }
                 self.snapshots.sort_by(|a, b| {
                     b.created
                         .and_utc()
@@ -140,47 +214,50 @@ impl Snapshots {
                 });
             }
             Message::RestoreSnapshot(snapshot) => {
-                if let Some(schema) = snapshot.schema {
-                    if let Err(e) = load_template(schema) {
-                        eprintln!("Failed to load template: {}", e);
-                    }
-                } else {
-                    log::warn!("Snapshot does not contain a valid schema.");
+                if let Err(e) = snapshot.restore() {
+                    return Report::new(fl!("failed-restore-snapshot"), e)
+                        .retry(crate::app::message::Message::Snapshots(
+                            Message::RestoreSnapshot(snapshot),
+                        ))
+                        .task();
                 }
+                return Task::done(crate::app::message::Message::Snapshots(Message::Restored(
+                    snapshot.id,
+                )));
             }
             Message::CreateSnapshot(name, kind) => {
-                let path = data_local_dir()
-                    .unwrap()
-                    .join(App::APP_ID)
-                    .join("snapshots");
-                if !path.exists()
-                    && let Err(e) = std::fs::create_dir_all(&path)
-                {
-                    log::error!("{e}");
-                }
-                let snapshot = Snapshot::new(name, kind);
-                match ron::to_string(&snapshot) {
-                    Ok(data) => {
-                        if let Err(e) = std::fs::write(snapshot.path(), data) {
-                            log::error!("Failed to write snapshot: {}", e);
-                        }
-                        log::info!("Snapshot created: {}", snapshot.name);
-                        tasks.push(self.update(Message::ReloadSnapshots));
-                    }
-                    Err(e) => {
-                        log::error!("Failed to serialize snapshot: {}", e);
-                    }
+                let snapshot = Snapshot::new(name.clone(), kind.clone());
+                if let Err(e) = snapshot.save() {
+                    return Report::new(fl!("failed-create-snapshot"), e)
+                        .retry(crate::app::message::Message::Snapshots(
+                            Message::CreateSnapshot(name, kind),
+                        ))
+                        .task();
                 }
+                return Task::done(crate::app::message::Message::Snapshots(Message::Changed));
             }
             Message::DeleteSnapshot(snapshot) => {
-                if snapshot.path().exists() {
-                    if let Err(e) = std::fs::remove_file(snapshot.path()) {
-                        log::error!("Failed to delete layout: {}", e);
-                        return Task::batch(tasks);
+                let deleted = snapshot.path().and_then(|path| {
+                    if path.exists() {
+                        std::fs::remove_file(path)?;
                     }
-                    tasks.push(self.update(Message::ReloadSnapshots));
+                    Ok(())
+                });
+                if let Err(e) = deleted {
+                    return Report::new(fl!("failed-delete-snapshot"), e).task();
+                }
+                return Task::done(crate::app::message::Message::Snapshots(Message::Changed));
+            }
+            // intercepted in the outer update fn to ask for confirmation
+            Message::DeleteIncompatible(_) => {}
+            Message::DeleteIncompatibleConfirmed(path) => {
+                if let Err(e) = std::fs::remove_file(&path) {
+                    return Report::new(fl!("failed-delete-snapshot"), e).task();
                 }
+                return Task::done(crate::app::message::Message::Snapshots(Message::Changed));
             }
+            Message::Changed => tasks.push(self.update(Message::ReloadSnapshots)),
+            Message::Restored(_) => {}
         }
         Task::batch(tasks)
     }
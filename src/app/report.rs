@@ -0,0 +1,108 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Local};
+use cosmic::{
+    Element, Task,
+    iced::{Alignment, Length},
+    widget,
+};
+
+use crate::app::{App, message::Message};
+use crate::{Error, fl};
+
+/// A failure carried up to the app, shown as a toast and kept in the error
+/// log of the session.
+#[derive(Debug, Clone)]
+pub struct Report {
+    /// What was being done, such as "Failed to apply the color scheme".
+    pub context: String,
+    pub error: Arc<Error>,
+    /// Sent again by the retry action, for failures that may not happen twice.
+    pub retry: Option<Box<Message>>,
+    pub time: DateTime<Local>,
+}
+
+impl Report {
+    pub fn new(context: String, error: impl Into<Error>) -> Self {
+        Self {
+            context,
+            error: Arc::new(error.into()),
+            retry: None,
+            time: Local::now(),
+        }
+    }
+
+    pub fn retry(mut self, message: Message) -> Self {
+        self.retry = Some(Box::new(message));
+        self
+    }
+
+    /// Sends the report up to the app, for pages that return app messages.
+    pub fn task(self) -> Task<Message> {
+        Task::done(Message::Error(self))
+    }
+}
+
+impl App {
+    /// Shows a failure as a toast and adds it to the error log.
+    ///
+    /// The toast offers to retry when the report can be retried, and to open
+    /// the log otherwise.
+    pub fn report(&mut self, report: Report) -> cosmic::app::Task<Message> {
+        log::error!("{}: {}", report.context, report.error);
+
+        let toast = widget::toaster::Toast::new(report.context.clone());
+        let toast = match report.retry.clone() {
+            Some(retry) => toast.action(fl!("retry"), move |_| (*retry).clone()),
+            None => toast.action(fl!("details"), |_| Message::ShowErrorLog),
+        };
+        self.cosmic.errors.push(report);
+
+        self.cosmic.toasts.push(toast).map(cosmic::action::app)
+    }
+
+    /// The failures of this session, newest first.
+    pub fn error_log<'a>(&'a self) -> Element<'a, Message> {
+        let spacing = cosmic::theme::spacing();
+
+        if self.cosmic.errors.is_empty() {
+            return widget::text::body(fl!("no-errors")).into();
+        }
+
+        let errors = self
+            .cosmic
+            .errors
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(index, report)| {
+                widget::row()
+                    .push(
+                        widget::column()
+                            .push(widget::text::caption(
+                                report.time.format("%H:%M:%S").to_string(),
+                            ))
+                            .push(widget::text::body(report.context.as_str()))
+                            .push(widget::text::caption(report.error.to_string()))
+                            .spacing(spacing.space_xxxs)
+                            .width(Length::Fill),
+                    )
+                    .push_maybe(report.retry.as_ref().map(|_| {
+                        widget::button::standard(fl!("retry")).on_press(Message::RetryError(index))
+                    }))
+                    .align_y(Alignment::Center)
+                    .spacing(spacing.space_xs)
+                    .into()
+            });
+
+        widget::column()
+            .push(widget::settings::section().extend(errors))
+            .push(
+                widget::row()
+                    .push(widget::horizontal_space())
+                    .push(widget::button::standard(fl!("clear")).on_press(Message::ClearErrors)),
+            )
+            .spacing(spacing.space_s)
+            .into()
+    }
+}
@@ -0,0 +1,175 @@
+use cosmic::{Application, Element, app::Task, iced::Length, widget};
+
+use crate::app::message::Message;
+use crate::app::page::Page;
+use crate::app::pages::{self, shortcuts::ShortcutsGroup};
+use crate::app::{App, Cosmic};
+use crate::fl;
+
+/// A setting or named item the global search can jump to.
+#[derive(Debug, Clone)]
+pub struct SearchItem {
+    pub page: Page,
+    pub title: String,
+    pub target: Target,
+}
+
+/// What is shown once the page of a search result is open.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Target {
+    /// A setting of the dock, the panel or the bar being edited, by the id
+    /// of its label.
+    Setting(&'static str),
+    ColorScheme(String),
+    Layout(String),
+    Panel(String),
+    Snapshot(String),
+    Shortcuts(ShortcutsGroup),
+}
+
+impl SearchItem {
+    pub fn new(page: Page, title: String, target: Target) -> Self {
+        Self {
+            page,
+            title,
+            target,
+        }
+    }
+}
+
+/// The global search, indexing the settings of every page.
+pub struct Search {
+    pub input: widget::Id,
+    pub query: String,
+    pub results: Vec<SearchItem>,
+}
+
+impl Default for Search {
+    fn default() -> Self {
+        Self {
+            input: widget::Id::unique(),
+            query: String::new(),
+            results: Vec::new(),
+        }
+    }
+}
+
+impl Search {
+    /// Runs the query against everything the pages currently list.
+    ///
+    /// Items whose title starts with the query come first.
+    pub fn set_query(&mut self, query: String, index: Vec<SearchItem>) {
+        let needle = query.trim().to_lowercase();
+        self.results = if needle.is_empty() {
+            Vec::new()
+        } else {
+            let mut results: Vec<SearchItem> = index
+                .into_iter()
+                .filter(|item| {
+                    item.title.to_lowercase().contains(&needle)
+                        || item.page.title().to_lowercase().contains(&needle)
+                })
+                .collect();
+            results.sort_by_key(|item| !item.title.to_lowercase().starts_with(&needle));
+            results
+        };
+        self.query = query;
+    }
+
+    pub fn view<'a>(&'a self) -> Element<'a, Message> {
+        let spacing = cosmic::theme::spacing();
+
+        let input = widget::search_input(fl!("search-settings"), &self.query)
+            .id(self.input.clone())
+            .on_input(Message::Search)
+            .on_clear(Message::Search(String::new()))
+            .on_submit(|_| Message::SearchSelect(0));
+
+        let results: Element<'a, Message> = if self.results.is_empty() {
+            widget::text::body(if self.query.trim().is_empty() {
+                fl!("search-settings-description")
+            } else {
+                fl!("no-results")
+            })
+            .into()
+        } else {
+            self.results
+                .iter()
+                .enumerate()
+                .fold(widget::list_column(), |list, (index, item)| {
+                    list.add(
+                        widget::button::custom(
+                            widget::column()
+                                .push(widget::text::body(item.title.as_str()))
+                                .push(widget::text::caption(item.page.title()))
+                                .width(Length::Fill),
+                        )
+                        .class(cosmic::style::Button::MenuItem)
+                        .on_press(Message::SearchSelect(index))
+                        .width(Length::Fill),
+                    )
+                })
+                .into()
+        };
+
+        widget::column()
+            .push(input)
+            .push(results)
+            .spacing(spacing.space_s)
+            .into()
+    }
+}
+
+impl App {
+    /// Opens the page of a search result and shows what was found.
+    pub fn show_search_result(&mut self, item: SearchItem) -> Task<Message> {
+        let mut tasks = vec![Cosmic::open_page(self, item.page)];
+        self.core_mut().window.show_context = false;
+
+        match item.target {
+            Target::Setting(key) => match item.page {
+                Page::Dock => self.dock.set_highlight(Some(key)),
+                Page::Panel => self.panel.set_highlight(Some(key)),
+                Page::Panels => self.panels.set_highlight(Some(key)),
+                Page::ColorSchemes
+                | Page::Applets
+                | Page::Layouts
+                | Page::Shortcuts
+                | Page::Snapshots => {}
+            },
+            Target::ColorScheme(name) => self.color_schemes.show(name),
+            Target::Layout(name) => self.layouts.show(name),
+            Target::Panel(name) => tasks.push(
+                self.panels
+                    .update(pages::panels::Message::Edit(name))
+                    .map(cosmic::action::app),
+            ),
+            Target::Snapshot(name) => self.snapshots.set_highlight(Some(name)),
+            Target::Shortcuts(group) => self.shortcuts.show(&group),
+        }
+
+        Task::batch(tasks)
+    }
+
+    /// Clears what the global search outlined once another page is opened.
+    pub fn clear_highlights(&mut self) {
+        self.dock.set_highlight(None);
+        self.panel.set_highlight(None);
+        self.panels.set_highlight(None);
+        self.snapshots.set_highlight(None);
+        self.color_schemes.set_highlight(None);
+    }
+
+    /// Everything the global search can find, collected from the pages.
+    pub fn search_index(&self) -> Vec<SearchItem> {
+        let mut index = Vec::new();
+        index.extend(self.color_schemes.search_items());
+        index.extend(self.dock.search_items());
+        index.extend(self.panel.search_items(Page::Panel));
+        index.extend(self.panels.search_items());
+        index.extend(self.layouts.search_items());
+        index.extend(self.shortcuts.search_items());
+        index.extend(self.snapshots.search_items());
+        index
+    }
+}
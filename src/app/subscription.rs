@@ -1,23 +1,37 @@
-use std::any::TypeId;
+use std::{
+    any::TypeId,
+    hash::{DefaultHasher, Hash, Hasher},
+};
 
 use cosmic::{
     Application,
-    cosmic_config::{self, Update},
-    cosmic_theme::{self, ThemeMode},
+    cosmic_config::{self, CosmicConfigEntry, Update},
+    cosmic_theme::{self, ThemeBuilder, ThemeMode},
     iced::{Event, Subscription, event, keyboard::Event as KeyEvent},
 };
+use cosmic_panel_config::CosmicPanelConfig;
+use cosmic_settings_config::shortcuts;
 
 use crate::app::message::{Message, SettingsMessage};
+use crate::app::pages::{
+    panel::{CosmicPanel, config::CosmicPanelButtonConfig},
+    shortcuts::ShortcutsConfig,
+};
 use crate::app::{App, core::config::TweaksConfig};
 
 use crate::app::core::config::CONFIG_VERSION;
 
-use super::Cosmic;
+use super::{Cosmic, dbus};
 
 impl Cosmic {
-    pub fn subscription() -> cosmic::iced::Subscription<Message> {
+    pub fn subscription(app: &App) -> cosmic::iced::Subscription<Message> {
         struct ConfigSubscription;
         struct ThemeSubscription;
+        struct ThemeBuilderSubscription;
+        struct PanelSubscription;
+        struct PanelEntriesSubscription;
+        struct PanelButtonsSubscription;
+        struct ShortcutsSubscription;
 
         let mut subscriptions = Vec::new();
 
@@ -70,6 +84,117 @@ impl Cosmic {
             }),
         );
 
+        for (is_dark, config_id) in [
+            (true, cosmic_theme::DARK_THEME_BUILDER_ID),
+            (false, cosmic_theme::LIGHT_THEME_BUILDER_ID),
+        ] {
+            subscriptions.push(
+                cosmic_config::config_subscription::<_, ThemeBuilder>(
+                    (TypeId::of::<ThemeBuilderSubscription>(), is_dark),
+                    config_id.into(),
+                    ThemeBuilder::VERSION,
+                )
+                .map(move |update: Update<ThemeBuilder>| {
+                    if !update.errors.is_empty() {
+                        log::info!(
+                            "errors loading theme {:?}: {:?}",
+                            update.keys,
+                            update.errors
+                        );
+                    }
+                    Message::SystemThemeChange(is_dark, Box::new(update.config))
+                }),
+            );
+        }
+
+        // Every panel entry is watched, along with the panel and dock pages
+        // which edit their entry whether it is shown or not.
+        let mut names = vec!["Panel".to_string(), "Dock".to_string()];
+        for name in app.panels.entries() {
+            if !names.contains(name) {
+                names.push(name.clone());
+            }
+        }
+        for name in names {
+            let mut hasher = DefaultHasher::new();
+            name.hash(&mut hasher);
+            subscriptions.push(
+                cosmic_config::config_subscription::<_, CosmicPanelConfig>(
+                    (TypeId::of::<PanelSubscription>(), hasher.finish()),
+                    format!("com.system76.CosmicPanel.{name}").into(),
+                    CosmicPanelConfig::VERSION,
+                )
+                .map(|update: Update<CosmicPanelConfig>| {
+                    if !update.errors.is_empty() {
+                        log::info!(
+                            "errors loading panel config {:?}: {:?}",
+                            update.keys,
+                            update.errors
+                        );
+                    }
+                    Message::PanelConfigChange(Box::new(update.config))
+                }),
+            );
+        }
+
+        subscriptions.push(
+            cosmic_config::config_subscription::<_, CosmicPanel>(
+                TypeId::of::<PanelEntriesSubscription>(),
+                "com.system76.CosmicPanel".into(),
+                1,
+            )
+            .map(|update: Update<CosmicPanel>| {
+                if !update.errors.is_empty() {
+                    log::info!(
+                        "errors loading panel entries {:?}: {:?}",
+                        update.keys,
+                        update.errors
+                    );
+                }
+                Message::PanelEntriesChange(update.config)
+            }),
+        );
+
+        subscriptions.push(
+            cosmic_config::config_subscription::<_, CosmicPanelButtonConfig>(
+                TypeId::of::<PanelButtonsSubscription>(),
+                "com.system76.CosmicPanelButton".into(),
+                CosmicPanelButtonConfig::VERSION,
+            )
+            .map(|update: Update<CosmicPanelButtonConfig>| {
+                if !update.errors.is_empty() {
+                    log::info!(
+                        "errors loading panel buttons {:?}: {:?}",
+                        update.keys,
+                        update.errors
+                    );
+                }
+                Message::PanelButtonsChange(update.config)
+            }),
+        );
+
+        subscriptions.push(
+            cosmic_config::config_subscription::<_, ShortcutsConfig>(
+                TypeId::of::<ShortcutsSubscription>(),
+                shortcuts::ID.into(),
+                ShortcutsConfig::VERSION,
+            )
+            .map(|update: Update<ShortcutsConfig>| {
+                if !update.errors.is_empty() {
+                    log::info!(
+                        "errors loading shortcuts {:?}: {:?}",
+                        update.keys,
+                        update.errors
+                    );
+                }
+                Message::ShortcutsChange(update.config.custom)
+            }),
+        );
+
+        if let Some(connection) = &app.cosmic.dbus {
+            subscriptions.push(dbus::subscription(connection.clone()));
+        }
+
         Subscription::batch(subscriptions)
     }
 }
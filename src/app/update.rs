@@ -1,8 +1,11 @@
 use cosmic::{
     Application, Task,
     app::{self},
+    iced::platform_specific::shell::commands::activation,
     widget::{self, menu::Action},
 };
+use cosmic_ext_config_templates::{Schema, panel::PanelSchema};
+use std::sync::atomic::Ordering;
 
 use crate::app::{
     App, dialog::DialogPage, message::SettingsMessage, pages::snapshots::config::SnapshotKind,
@@ -10,8 +13,17 @@ use crate::app::{
 use crate::app::{message::Message, pages::layouts::dialog::CreateLayoutDialog};
 
 use super::Cosmic;
-use crate::app::core::config::AppTheme;
-use crate::app::pages::{self, layouts::preview::Position};
+use crate::app::core::{
+    cli::{Dialog, Request},
+    config::AppTheme,
+    profile::Profile,
+};
+use crate::app::dbus::Signal;
+use crate::app::message::ProfileMessage;
+use crate::app::pages::layouts::preset::DesktopState;
+use crate::app::report::Report;
+use crate::app::{context::ContextPage, page::Page, pages};
+use crate::{Error, fl};
 
 impl Cosmic {
     pub fn update(app: &mut App, message: Message) -> app::Task<Message> {
@@ -19,7 +31,7 @@ impl Cosmic {
         match message {
             Message::Open(url) => {
                 if let Err(err) = open::that_detached(url) {
-                    log::error!("{err}")
+                    tasks.push(app.report(Report::new(fl!("failed-open-link"), err)));
                 }
             }
             Message::ToggleContextPage(page) => {
@@ -29,21 +41,90 @@ impl Cosmic {
                     app.cosmic.context_page = page;
                     app.core_mut().window.show_context = true;
                 }
+                if app.cosmic.context_page == ContextPage::Search && app.core().window.show_context
+                {
+                    tasks.push(widget::text_input::focus(app.cosmic.search.input.clone()));
+                }
+            }
+            Message::Search(query) => {
+                let index = app.search_index();
+                app.cosmic.search.set_query(query, index);
+            }
+            Message::SearchSelect(index) => {
+                if let Some(item) = app.cosmic.search.results.get(index).cloned() {
+                    tasks.push(app.show_search_result(item));
+                }
             }
             Message::ToggleContextDrawer => {
                 app.core_mut().window.show_context = !app.core().window.show_context;
             }
             Message::Dock(message) => tasks.push(app.dock.update(message).map(cosmic::action::app)),
-            Message::Panel(message) => {
-                tasks.push(app.panel.update(message).map(cosmic::action::app))
-            }
-            Message::Layouts(message) => {
-                tasks.push(app.layouts.update(message).map(cosmic::action::app))
+            Message::Panel(message) => tasks.push(
+                app.panel
+                    .update(message, Message::Panel)
+                    .map(cosmic::action::app),
+            ),
+            Message::Applets(message) => {
+                tasks.push(app.applets.update(message).map(cosmic::action::app))
             }
+            Message::Panels(message) => match message {
+                pages::panels::Message::Delete(name) => {
+                    tasks.push(app.update(Message::ToggleDialogPage(DialogPage::DeletePanel(name))))
+                }
+                message => tasks.push(app.panels.update(message).map(cosmic::action::app)),
+            },
+            Message::Layouts(message) => match message {
+                pages::layouts::Message::SaveCurrentLayout => match PanelSchema::generate() {
+                    Ok(panel_schema) => tasks.push(app.update(Message::ToggleDialogPage(
+                        DialogPage::CreateLayout(CreateLayoutDialog::new(
+                            String::new(),
+                            Schema::Panel(panel_schema),
+                            None,
+                        )),
+                    ))),
+                    Err(e) => tasks.push(app.report(Report::new(
+                        fl!("failed-read-layout"),
+                        Error::Template(e.to_string()),
+                    ))),
+                },
+                pages::layouts::Message::SaveCurrentPreset => tasks.push(app.update(
+                    Message::ToggleDialogPage(DialogPage::CreatePreset(String::new())),
+                )),
+                _ => tasks.push(app.layouts.update(message).map(cosmic::action::app)),
+            },
             Message::Shortcuts(message) => {
-                tasks.push(app.shortcuts.update(message).map(cosmic::action::app))
+                // Schemes that replace current shortcuts ask how to resolve them first.
+                if let pages::shortcuts::Message::ApplyShortcuts(group) = &message
+                    && let Some(dialog) = app.shortcuts.resolve(group)
+                {
+                    tasks.push(
+                        app.update(Message::ToggleDialogPage(DialogPage::ResolveShortcuts(
+                            dialog,
+                        ))),
+                    )
+                } else if let pages::shortcuts::Message::SaveAsGroup = message {
+                    tasks.push(app.update(Message::ToggleDialogPage(
+                        DialogPage::CreateShortcutGroup(String::new()),
+                    )))
+                } else {
+                    tasks.push(app.shortcuts.update(message).map(cosmic::action::app))
+                }
             }
             Message::Snapshots(message) => {
+                match &message {
+                    pages::snapshots::Message::DeleteIncompatible(path) => {
+                        return app.update(Message::ToggleDialogPage(
+                            DialogPage::DeleteIncompatibleSnapshot(path.clone()),
+                        ));
+                    }
+                    pages::snapshots::Message::Changed => {
+                        tasks.push(app.emit(Signal::SnapshotsChanged))
+                    }
+                    pages::snapshots::Message::Restored(id) => {
+                        tasks.push(app.emit(Signal::SnapshotRestored(*id)))
+                    }
+                    _ => {}
+                }
                 tasks.push(app.snapshots.update(message).map(cosmic::action::app))
             }
             Message::ColorSchemes(message) => match *message {
@@ -52,32 +133,19 @@ impl Cosmic {
                         DialogPage::SaveCurrentColorScheme(String::new()),
                     )))
                 }
-                _ => tasks.push(
-                    app.color_schemes
-                        .update(*message)
-                        .map(Box::new)
-                        .map(Message::ColorSchemes)
-                        .map(cosmic::action::app),
-                ),
-            },
-            Message::UpdatePanelLayoutPosition(entity, name, mut preview) => {
-                app.layouts.panel_model.activate(entity);
-                if let Some(position) = app.layouts.panel_model.data::<Position>(entity) {
-                    preview.panel.position = *position;
-                    tasks.push(app.update(Message::DialogUpdate(DialogPage::CreateLayout(
-                        CreateLayoutDialog::new(name, preview, None),
-                    ))))
-                }
-            }
-            Message::UpdateDockLayoutPosition(entity, name, mut preview) => {
-                app.layouts.dock_model.activate(entity);
-                if let Some(position) = app.layouts.dock_model.data::<Position>(entity) {
-                    preview.dock.position = *position;
-                    tasks.push(app.update(Message::DialogUpdate(DialogPage::CreateLayout(
-                        CreateLayoutDialog::new(name, preview, None),
-                    ))))
+                message => {
+                    if let pages::color_schemes::Message::Error(_, report) = &message {
+                        tasks.push(app.report(report.clone()));
+                    }
+                    tasks.push(
+                        app.color_schemes
+                            .update(message)
+                            .map(Box::new)
+                            .map(Message::ColorSchemes)
+                            .map(cosmic::action::app),
+                    )
                 }
-            }
+            },
             Message::SaveNewColorScheme(name) => {
                 tasks.push(app.update(Message::ColorSchemes(Box::new(
                     pages::color_schemes::Message::SaveCurrentColorScheme(Some(name)),
@@ -103,23 +171,56 @@ impl Cosmic {
                                 pages::snapshots::Message::CreateSnapshot(name, SnapshotKind::User),
                             )))
                         }
+                        DialogPage::CreatePreset(name) => tasks.push(app.update(Message::Layouts(
+                            pages::layouts::Message::CreatePreset(name),
+                        ))),
+                        DialogPage::ResolveShortcuts(dialog) => {
+                            if let Some(resolutions) = dialog.resolutions() {
+                                tasks.push(app.update(Message::Shortcuts(
+                                    pages::shortcuts::Message::ApplyResolved(
+                                        dialog.scheme.group,
+                                        resolutions,
+                                    ),
+                                )))
+                            }
+                        }
+                        DialogPage::CreateProfile(name) => {
+                            tasks.push(app.update(Message::Profile(ProfileMessage::Save(name))))
+                        }
+                        DialogPage::DeletePanel(name) => tasks.push(app.update(Message::Panels(
+                            pages::panels::Message::DeleteConfirmed(name),
+                        ))),
+                        DialogPage::DeleteIncompatibleSnapshot(path) => {
+                            tasks.push(app.update(Message::Snapshots(
+                                pages::snapshots::Message::DeleteIncompatibleConfirmed(path),
+                            )))
+                        }
+                        DialogPage::SwitchProfile(index) => {
+                            tasks.push(app.update(Message::Profile(ProfileMessage::Switch(index))))
+                        }
+                        DialogPage::DeleteProfile(index) => tasks.push(
+                            app.update(Message::Profile(ProfileMessage::DeleteConfirmed(index))),
+                        ),
+                        DialogPage::CreateShortcutGroup(name) => tasks.push(app.update(
+                            Message::Shortcuts(pages::shortcuts::Message::CreateGroup(name)),
+                        )),
                         DialogPage::CreateLayout(dialog) => {
                             let CreateLayoutDialog {
                                 name,
-                                preview,
+                                schema,
                                 error,
                             } = dialog;
                             if let Some(error) = error {
                                 tasks.push(app.update(Message::ToggleDialogPage(
                                     DialogPage::CreateLayout(CreateLayoutDialog::new(
                                         name,
-                                        preview,
+                                        schema,
                                         Some(error),
                                     )),
                                 )));
                             } else {
                                 tasks.push(app.update(Message::Layouts(
-                                    pages::layouts::Message::Create(name, preview),
+                                    pages::layouts::Message::Create(name, schema),
                                 )));
                             }
                         }
@@ -130,6 +231,14 @@ impl Cosmic {
                 app.cosmic.dialog_pages.pop_front();
             }
             Message::Key(modifiers, key) => {
+                // The shortcut editor takes key presses as a binding while it listens.
+                if app.shortcuts.is_capturing()
+                    && app.cosmic.nav_model.active_data::<Page>() == Some(&Page::Shortcuts)
+                {
+                    return app.update(Message::Shortcuts(pages::shortcuts::Message::Captured(
+                        modifiers, key,
+                    )));
+                }
                 for (key_bind, action) in &app.cosmic.key_binds {
                     if key_bind.matches(modifiers, &key) {
                         return app.update(action.message());
@@ -143,6 +252,211 @@ impl Cosmic {
                 app.color_schemes.set_theme_mode(theme_mode);
                 tasks.push(app.set_theme());
             }
+            Message::SystemThemeChange(is_dark, theme_builder) => {
+                if app.color_schemes.sync_theme(is_dark, *theme_builder) {
+                    tasks.push(app.emit(Signal::ColorSchemeChanged));
+                }
+                tasks.push(app.check_profile_drift());
+            }
+            Message::PanelConfigChange(config) => {
+                app.panel.sync_config((*config).clone());
+                app.dock.sync_config((*config).clone());
+                app.panels.sync_config((*config).clone());
+                app.applets.sync_config(*config);
+                tasks.push(app.check_profile_drift());
+            }
+            Message::PanelEntriesChange(config) => {
+                app.panel.sync_entries(config.clone());
+                app.dock.sync_entries(config.clone());
+                app.panels.sync_entries(config);
+                app.applets.reload_panels();
+                tasks.push(app.check_profile_drift());
+            }
+            Message::PanelButtonsChange(config) => {
+                app.panel.sync_buttons(config.clone());
+                app.dock.sync_buttons(config.clone());
+                app.panels.sync_buttons(config);
+                tasks.push(app.check_profile_drift());
+            }
+            Message::ShortcutsChange(shortcuts) => {
+                app.shortcuts.sync_custom(shortcuts);
+                tasks.push(app.check_profile_drift());
+            }
+            Message::Error(report) => tasks.push(app.report(report)),
+            Message::RetryError(index) => {
+                if let Some(retry) = app.cosmic.errors.get(index).and_then(|r| r.retry.clone()) {
+                    tasks.push(app.update(*retry));
+                }
+            }
+            Message::ClearErrors => app.cosmic.errors.clear(),
+            Message::ShowErrorLog => {
+                app.cosmic.context_page = ContextPage::ErrorLog;
+                app.core_mut().window.show_context = true;
+            }
+            Message::CloseToast(id) => app.cosmic.toasts.remove(id),
+            Message::Activate(request, token) => {
+                if let Some(id) = app.core().main_window_id() {
+                    // Compositors only hand focus over to a window presenting a
+                    // token from the launch that asked for it.
+                    tasks.push(match token {
+                        Some(token) => activation::activate(id, token),
+                        None => cosmic::iced::window::gain_focus(id),
+                    });
+                }
+                match request {
+                    Some(Request::Page(page)) => tasks.push(Cosmic::open_page(app, page)),
+                    Some(Request::Dialog(dialog)) => {
+                        tasks.push(Cosmic::open_page(app, dialog.page()));
+                        let message = match dialog {
+                            Dialog::SaveScheme => Message::ColorSchemes(Box::new(
+                                pages::color_schemes::Message::SaveCurrentColorScheme(None),
+                            )),
+                            Dialog::ImportScheme => Message::ColorSchemes(Box::new(
+                                pages::color_schemes::Message::StartImport,
+                            )),
+                            Dialog::SaveLayout => {
+                                Message::Layouts(pages::layouts::Message::SaveCurrentLayout)
+                            }
+                            Dialog::ImportLayout => {
+                                Message::Layouts(pages::layouts::Message::StartImport)
+                            }
+                            Dialog::ImportShortcuts => {
+                                Message::Shortcuts(pages::shortcuts::Message::StartImport)
+                            }
+                            Dialog::CreateSnapshot => {
+                                Message::ToggleDialogPage(DialogPage::CreateSnapshot(String::new()))
+                            }
+                        };
+                        tasks.push(app.update(message));
+                    }
+                    Some(Request::ApplyScheme(name)) => {
+                        let installed = pages::color_schemes::installed_system_themes()
+                            .is_ok_and(|schemes| schemes.iter().any(|s| s.name == name));
+                        if installed {
+                            tasks.push(app.update(Message::ColorSchemes(Box::new(
+                                pages::color_schemes::Message::SetColorScheme(
+                                    pages::color_schemes::ColorSchemeKey::Installed(name),
+                                ),
+                            ))));
+                        } else {
+                            tasks.push(app.report(Report::new(
+                                fl!("failed-apply-color-scheme"),
+                                Error::ColorScheme(format!("{name} is not installed")),
+                            )));
+                        }
+                    }
+                    None => {}
+                }
+            }
+            Message::Profile(message) => match message {
+                ProfileMessage::Switch(index) => {
+                    let Some(profile) = app.config.profiles.get(index).cloned() else {
+                        return Task::none();
+                    };
+                    match profile.apply() {
+                        Ok(()) => {
+                            if let Err(err) = app
+                                .config
+                                .set_active_profile(&app.handler, Some(profile.id))
+                            {
+                                tasks.push(
+                                    app.report(Report::new(fl!("failed-save-settings"), err)),
+                                );
+                            }
+                            tasks.push(app.check_profile_drift());
+                        }
+                        Err(e) => tasks.push(
+                            app.report(
+                                Report::new(fl!("failed-switch-profile"), e)
+                                    .retry(Message::Profile(ProfileMessage::Switch(index))),
+                            ),
+                        ),
+                    }
+                }
+                ProfileMessage::Next => {
+                    let profiles = &app.config.profiles;
+                    if !profiles.is_empty() {
+                        let next = profiles
+                            .iter()
+                            .position(|profile| Some(profile.id) == app.config.active_profile)
+                            .map_or(0, |index| (index + 1) % profiles.len());
+                        tasks.push(
+                            app.update(Message::ToggleDialogPage(DialogPage::SwitchProfile(next))),
+                        );
+                    }
+                }
+                ProfileMessage::Save(name) => match Profile::current(name) {
+                    Ok(profile) => {
+                        let id = profile.id;
+                        let mut profiles = app.config.profiles.clone();
+                        profiles.push(profile);
+                        if let Err(err) = app
+                            .config
+                            .set_profiles(&app.handler, profiles)
+                            .and_then(|_| app.config.set_active_profile(&app.handler, Some(id)))
+                        {
+                            tasks.push(app.report(Report::new(fl!("failed-save-profile"), err)));
+                        }
+                        app.cosmic.profile_drift = false;
+                        app.cosmic
+                            .profile_drift_check
+                            .fetch_add(1, Ordering::Relaxed);
+                    }
+                    Err(e) => tasks.push(app.report(Report::new(fl!("failed-save-profile"), e))),
+                },
+                ProfileMessage::Update(index) => {
+                    let mut profiles = app.config.profiles.clone();
+                    let Some(profile) = profiles.get_mut(index) else {
+                        return Task::none();
+                    };
+                    match DesktopState::capture() {
+                        Ok(state) => {
+                            profile.state = state;
+                            if let Err(err) = app.config.set_profiles(&app.handler, profiles) {
+                                tasks
+                                    .push(app.report(Report::new(fl!("failed-save-profile"), err)));
+                            }
+                            tasks.push(app.check_profile_drift());
+                        }
+                        Err(e) => {
+                            tasks.push(app.report(Report::new(fl!("failed-save-profile"), e)))
+                        }
+                    }
+                }
+                ProfileMessage::Delete(index) => {
+                    if index < app.config.profiles.len() {
+                        tasks.push(
+                            app.update(Message::ToggleDialogPage(DialogPage::DeleteProfile(index))),
+                        );
+                    }
+                }
+                ProfileMessage::DeleteConfirmed(index) => {
+                    let mut profiles = app.config.profiles.clone();
+                    if index < profiles.len() {
+                        let profile = profiles.remove(index);
+                        let active = app.config.active_profile == Some(profile.id);
+                        if let Err(err) =
+                            app.config
+                                .set_profiles(&app.handler, profiles)
+                                .and_then(|_| {
+                                    if active {
+                                        app.config.set_active_profile(&app.handler, None)
+                                    } else {
+                                        Ok(())
+                                    }
+                                })
+                        {
+                            tasks.push(app.report(Report::new(fl!("failed-delete-profile"), err)));
+                        }
+                        tasks.push(app.check_profile_drift());
+                    }
+                }
+            },
+            Message::ProfileDrift(check, drifted) => {
+                if check == app.cosmic.profile_drift_check.load(Ordering::Relaxed) {
+                    app.cosmic.profile_drift = drifted;
+                }
+            }
             Message::Settings(settings_message) => match settings_message {
                 SettingsMessage::AppTheme(index) => {
                     let app_theme = match index {
@@ -151,12 +465,80 @@ impl Cosmic {
                         _ => AppTheme::System,
                     };
                     if let Err(err) = app.config.set_app_theme(&app.handler, app_theme) {
-                        log::warn!("failed to save config: {}", err);
+                        tasks.push(
+                            app.report(
+                                Report::new(fl!("failed-save-settings"), err)
+                                    .retry(Message::Settings(SettingsMessage::AppTheme(index))),
+                            ),
+                        );
                     };
                     tasks.push(app.set_theme());
                 }
+                SettingsMessage::LayoutCatalogUrl(url) => {
+                    if let Err(err) = app.config.set_layout_catalog_url(&app.handler, url.clone()) {
+                        tasks.push(
+                            app.report(
+                                Report::new(fl!("failed-save-settings"), err).retry(
+                                    Message::Settings(SettingsMessage::LayoutCatalogUrl(url)),
+                                ),
+                            ),
+                        );
+                    }
+                }
+                SettingsMessage::HiddenLayouts(hidden) => {
+                    if let Err(err) = app.config.set_hidden_layouts(&app.handler, hidden.clone()) {
+                        tasks.push(
+                            app.report(
+                                Report::new(fl!("failed-save-settings"), err).retry(
+                                    Message::Settings(SettingsMessage::HiddenLayouts(hidden)),
+                                ),
+                            ),
+                        );
+                    }
+                }
+                SettingsMessage::ShortcutSchemes(schemes) => {
+                    if let Err(err) = app
+                        .config
+                        .set_shortcut_schemes(&app.handler, schemes.clone())
+                    {
+                        tasks.push(app.report(
+                            Report::new(fl!("failed-save-settings"), err).retry(Message::Settings(
+                                SettingsMessage::ShortcutSchemes(schemes),
+                            )),
+                        ));
+                    }
+                }
+                SettingsMessage::AppliedLayout(layout) => {
+                    if let Some(id) = layout {
+                        tasks.push(app.emit(Signal::LayoutApplied(id)));
+                    }
+                    tasks.push(
+                        app.update(Message::Settings(SettingsMessage::SaveAppliedLayout(
+                            layout,
+                        ))),
+                    );
+                }
+                SettingsMessage::SaveAppliedLayout(layout) => {
+                    if let Err(err) = app.config.set_applied_layout(&app.handler, layout) {
+                        tasks.push(app.report(
+                            Report::new(fl!("failed-save-settings"), err).retry(Message::Settings(
+                                SettingsMessage::SaveAppliedLayout(layout),
+                            )),
+                        ));
+                    }
+                }
                 SettingsMessage::ConfigUpdate(config) => {
+                    tasks.push(app.update(Message::Layouts(
+                        pages::layouts::Message::SetCatalogUrl(config.layout_catalog_url.clone()),
+                    )));
+                    tasks.push(app.update(Message::Layouts(
+                        pages::layouts::Message::SetHiddenLayouts(config.hidden_layouts.clone()),
+                    )));
+                    tasks.push(app.update(Message::Shortcuts(
+                        pages::shortcuts::Message::SetApplied(config.shortcut_schemes.clone()),
+                    )));
                     app.config = config;
+                    tasks.push(app.check_profile_drift());
                     tasks.push(app.set_theme());
                 }
             },
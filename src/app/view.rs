@@ -26,17 +26,20 @@ impl Cosmic {
                 .map(Message::ColorSchemes),
             Page::Dock => app.dock.view().map(Message::Dock),
             Page::Panel => app.panel.view().map(Message::Panel),
+            Page::Panels => app.panels.view().map(Message::Panels),
+            Page::Applets => app.applets.view().map(Message::Applets),
             Page::Layouts => app.layouts.view().map(Message::Layouts),
             Page::Snapshots => app.snapshots.view().map(Message::Snapshots),
             Page::Shortcuts => app.shortcuts.view().map(Message::Shortcuts),
         };
 
-        widget::column()
+        let content = widget::column()
             .push(view)
             .padding(spacing.space_xs)
             .width(Length::Fill)
             .height(Length::Fill)
-            .align_x(Alignment::Center)
-            .into()
+            .align_x(Alignment::Center);
+
+        widget::toaster(&app.cosmic.toasts, content)
     }
 }
@@ -1,5 +1,5 @@
 pub use app::core::error::Error;
-use app::core::settings;
+use app::core::{cli::Command, settings};
 
 #[allow(unused_imports)]
 #[macro_use]
@@ -12,6 +12,14 @@ mod app;
 
 fn main() -> Result<(), Error> {
     settings::init()?;
-    cosmic::app::run::<app::App>(settings::settings(), settings::flags())?;
+    match Command::parse(std::env::args().skip(1))? {
+        Command::Gui(request) => match app::dbus::claim(request.as_ref())? {
+            app::dbus::Instance::Primary(dbus) => {
+                cosmic::app::run::<app::App>(settings::settings(), settings::flags(request, dbus))?
+            }
+            app::dbus::Instance::Forwarded => {}
+        },
+        command => command.run()?,
+    }
     Ok(())
 }